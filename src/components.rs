@@ -2,9 +2,28 @@ use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::game::player::components::{Player, Spawn};
-use crate::game::enemies::components::{Zombie, Enemy};
+use crate::game::player::components::{Player, PlayerId, InputDevice, PlayerActions};
+use crate::game::player::systems::PLAYER_HEALTH;
+use crate::game::enemies::components::{Zombie, Enemy, LastHit};
 use crate::graphics::FrameAnimation;
+use crate::AppState;
+
+/// Where a moving body was after the last two fixed steps. Its `InterpolatedSprite` child draws it
+/// in between them, so movement looks smooth at frame rates above the fixed timestep.
+/// The body's own `Transform` always stays where the simulation has it.
+#[derive(Copy, Clone, PartialEq, Debug, Component)]
+pub struct Interpolated {
+    pub previous: Vec3,
+    pub current: Vec3,
+    /// Translation it is drawn at this frame
+    pub rendered: Vec3,
+    pub sprite: Entity,
+}
+
+/// Child drawing a copy of the sprite of its body, offset to where the body is drawn.
+/// The body itself is hidden.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
+pub struct InterpolatedSprite;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
 pub struct Wall;
@@ -14,14 +33,13 @@ pub struct WallBundle {
     wall: Wall,
 }   
 
-#[derive(Clone, Debug, Default, Bundle, LdtkIntCell)]
-pub struct SpawnBundle {
-    spawn: Spawn,
-}
-
 #[derive(Component)]
 pub struct MainCamera;
 
+/// Despawned together with its children once `AppState` leaves the given state
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Component)]
+pub struct StateScoped(pub AppState);
+
 #[derive(Clone, Default, Bundle)]
 pub struct PlayerBundle {
     //#[sprite_bundle("player.png")]
@@ -31,7 +49,11 @@ pub struct PlayerBundle {
     #[bundle]
     pub collider_bundle: ColliderBundle,
     pub player: Player,
+    pub player_id: PlayerId,
+    pub input_device: InputDevice,
+    pub actions: PlayerActions,
     pub weapon: Weapon, 
+    pub inventory: Inventory,
     //#[worldly]
     pub worldly: Worldly,
 
@@ -42,7 +64,8 @@ pub struct PlayerBundle {
     // The whole EntityInstance can be stored directly as an EntityInstance component
     //#[from_entity_instance]
     pub entity_instance: EntityInstance,
-    pub health: Health
+    pub health: Health,
+    pub max_health: MaxHealth,
 }
 
 #[derive(Clone, Default, Component)]
@@ -90,6 +113,21 @@ impl Default for ReloadTimer {
     }
 }
 
+#[derive(Clone, Component, Debug)]
+pub struct Inventory {
+    pub grenades: u32,
+    pub keys: Vec<String>,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self {
+            grenades: 3,
+            keys: Vec::new(),
+        }
+    }
+}
+
 #[derive(Clone, Default, Bundle, LdtkEntity)]
 pub struct ZombieBundle {
     #[sprite_bundle("zombie.png")]
@@ -105,6 +143,7 @@ pub struct ZombieBundle {
     #[from_entity_instance]
     pub entity_instance: EntityInstance,
     pub health: Health,
+    pub last_hit: LastHit,
     pub animations: FrameAnimation,
 }
 
@@ -121,14 +160,29 @@ impl Default for Health{
     }
 }
 
+/// Health a player heals up to, what its loadout started it with
+#[derive(Clone, Component, Debug, Eq, PartialEq)]
+pub struct MaxHealth {
+    pub health_points: i32,
+}
+
+impl Default for MaxHealth {
+    fn default() -> Self {
+        Self {
+            health_points: PLAYER_HEALTH,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
 pub struct ShadowCaster;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
 pub struct Bullet;
 
-#[derive(Copy, Clone, PartialEq, Debug, Default, Component)]
-pub struct Target {pub target: Vec2}
+/// Player or buddy that fired a bullet
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Component)]
+pub struct Shooter(pub Entity);
 
 #[derive(Clone, Default, Bundle)]
 pub struct BulletBundle {
@@ -189,6 +243,16 @@ impl From<&EntityInstance> for ColliderBundle {
                 rotation_constraints,
                 ..Default::default()
             },
+            "Npc" => ColliderBundle {
+                collider: Collider::cuboid(3., 6.),
+                rigid_body: RigidBody::Fixed,
+                ..Default::default()
+            },
+            "HealthPack" | "AmmoBox" | "GrenadePickup" | "WeaponPickup" | "Key" => ColliderBundle {
+                collider: Collider::cuboid(6.0, 6.0),
+                rigid_body: RigidBody::Fixed,
+                ..Default::default()
+            },
             _ => ColliderBundle::default(),
         }
     }
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+/// Sent when the selected level changes to the level `iid`
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LevelEntered {
+    pub iid: String,
+}
+
+/// Sent when the level `iid` stops being the selected level
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LevelExited {
+    pub iid: String,
+}
+
+/// Sent when a bullet of a player hits a zombie, at the zombie's position
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BulletHit {
+    pub position: Vec2,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GrenadeExploded {
+    pub position: Vec2,
+}
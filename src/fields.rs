@@ -0,0 +1,39 @@
+use bevy_ecs_ldtk::ldtk::{FieldInstance, FieldValue};
+
+// Helpers for reading LDtk custom fields of entities and levels
+
+pub fn field_value<'a>(fields: &'a [FieldInstance], identifier: &str) -> Option<&'a FieldValue> {
+    fields
+        .iter()
+        .find(|field| field.identifier == identifier)
+        .map(|field| &field.value)
+}
+
+pub fn int_field(fields: &[FieldInstance], identifier: &str) -> Option<i32> {
+    match field_value(fields, identifier) {
+        Some(FieldValue::Int(value)) => *value,
+        _ => None,
+    }
+}
+
+pub fn float_field(fields: &[FieldInstance], identifier: &str) -> Option<f32> {
+    match field_value(fields, identifier) {
+        Some(FieldValue::Float(value)) => *value,
+        Some(FieldValue::Int(value)) => value.map(|value| value as f32),
+        _ => None,
+    }
+}
+
+pub fn bool_field(fields: &[FieldInstance], identifier: &str) -> Option<bool> {
+    match field_value(fields, identifier) {
+        Some(FieldValue::Bool(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+pub fn string_field(fields: &[FieldInstance], identifier: &str) -> Option<String> {
+    match field_value(fields, identifier) {
+        Some(FieldValue::String(value)) | Some(FieldValue::Enum(value)) => value.clone(),
+        _ => None,
+    }
+}
@@ -0,0 +1,39 @@
+use std::env;
+
+use bevy::prelude::*;
+
+/// Seconds a bot waits after throwing a grenade, so one crowd isn't showered with all of them
+pub const BOT_GRENADE_COOLDOWN: f32 = 3.0;
+
+/// `--bot` hands player one over to a bot, to leave the game running on its own
+#[derive(Resource, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct BotTakeover(pub bool);
+
+impl BotTakeover {
+    pub fn from_args() -> BotTakeover {
+        BotTakeover(env::args().skip(1).any(|arg| arg == "--bot"))
+    }
+}
+
+/// Points every bot walks between in a loop, standing still without any
+#[derive(Resource, Clone, Debug, Default)]
+pub struct BotRoute {
+    pub waypoints: Vec<Vec2>,
+}
+
+/// What a player driven by `InputDevice::Bot` is up to, added once the player spawns
+#[derive(Clone, Debug, Component)]
+pub struct BotBrain {
+    /// Index of the `BotRoute` waypoint it walks to
+    pub waypoint: usize,
+    pub grenade_cooldown: Timer,
+}
+
+impl Default for BotBrain {
+    fn default() -> Self {
+        Self {
+            waypoint: 0,
+            grenade_cooldown: Timer::from_seconds(BOT_GRENADE_COOLDOWN, TimerMode::Once),
+        }
+    }
+}
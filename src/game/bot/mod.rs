@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+mod systems;
+pub mod components;
+
+use components::{BotRoute, BotTakeover};
+use systems::*;
+
+use crate::{AppState, SimulationState};
+use crate::game::player::input::read_player_input;
+use crate::replay::systems::{not_playing_back, record_frame};
+
+/// Players with `InputDevice::Bot` play on their own, for long unattended runs.
+/// `--bot` lets one play the game too.
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BotRoute>()
+        .insert_resource(BotTakeover::from_args())
+        .add_systems(
+            (
+                take_over_player_one,
+                add_bot_brains.after(take_over_player_one),
+                // A recording holds what bots did like it holds what humans did
+                drive_bots
+                    .run_if(not_playing_back)
+                    .after(read_player_input)
+                    .before(record_frame),
+            )
+            .in_set(OnUpdate(AppState::Game))
+            .in_set(OnUpdate(SimulationState::Running))
+        );
+    }
+}
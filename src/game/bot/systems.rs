@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::components::{Inventory, Weapon};
+use crate::game::enemies::components::Zombie;
+use crate::game::player::components::{InputDevice, Player, PlayerActions, PlayerId};
+use super::components::*;
+
+/// How close a waypoint has to be to count as reached
+const WAYPOINT_REACHED: f32 = 16.0;
+/// Zombies further away than this are left alone
+const BOT_SIGHT: f32 = 250.0;
+/// Zombies this close to the one aimed at make up a crowd with it
+const CROWD_RADIUS: f32 = 50.0;
+/// Zombies in a crowd worth a grenade
+const CROWD_SIZE: usize = 3;
+
+/// Player one is played by a bot instead of the keyboard and mouse with `--bot`
+pub fn take_over_player_one(
+    takeover: Res<BotTakeover>,
+    mut player_query: Query<(&PlayerId, &mut InputDevice), Added<Player>>,
+) {
+    if !takeover.0 {
+        return;
+    }
+    for (player_id, mut device) in player_query.iter_mut() {
+        if player_id.0 == 0 && *device == InputDevice::KeyboardMouse {
+            *device = InputDevice::Bot;
+        }
+    }
+}
+
+/// Bots that spawned or respawned get a brain of their own
+#[allow(clippy::type_complexity)]
+pub fn add_bot_brains(
+    mut commands: Commands,
+    player_query: Query<(Entity, &InputDevice), (With<Player>, Without<BotBrain>)>,
+) {
+    for (player, device) in player_query.iter() {
+        if *device == InputDevice::Bot {
+            commands.entity(player).insert(BotBrain::default());
+        }
+    }
+}
+
+/// Fills the actions of bots the way `read_player_input` does for everyone else.
+/// Bots walk the `BotRoute`, shoot the nearest zombie they see, reload once empty
+/// and throw grenades into crowds.
+pub fn drive_bots(
+    time: Res<Time>,
+    route: Res<BotRoute>,
+    mut bot_query: Query<(&mut BotBrain, &mut PlayerActions, &Transform, &Weapon, &Inventory), With<Player>>,
+    zombie_query: Query<&Transform, With<Zombie>>,
+) {
+    let zombies: Vec<Vec2> = zombie_query
+        .iter()
+        .map(|zombie_transform| zombie_transform.translation.truncate())
+        .collect();
+
+    for (mut brain, mut actions, transform, weapon, inventory) in bot_query.iter_mut() {
+        let position = transform.translation.truncate();
+
+        actions.movement = if route.waypoints.is_empty() {
+            Vec2::ZERO
+        } else {
+            brain.waypoint %= route.waypoints.len();
+            if position.distance(route.waypoints[brain.waypoint]) < WAYPOINT_REACHED {
+                brain.waypoint = (brain.waypoint + 1) % route.waypoints.len();
+            }
+            (route.waypoints[brain.waypoint] - position).normalize_or_zero()
+        };
+
+        let target = zombies
+            .iter()
+            .copied()
+            .filter(|zombie| zombie.distance(position) < BOT_SIGHT)
+            .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+
+        // Holding the trigger keeps firing, like a human would
+        let firing = target.is_some() && weapon.ammo.bullets > 0 && !weapon.reloading;
+        actions.fire_just_pressed |= firing && !actions.fire;
+        actions.fire = firing;
+        actions.reload |= weapon.ammo.bullets == 0 && !weapon.reloading;
+
+        brain.grenade_cooldown.tick(time.delta());
+        if let Some(target) = target {
+            actions.aim = target;
+            let crowd = zombies
+                .iter()
+                .filter(|zombie| zombie.distance(target) < CROWD_RADIUS)
+                .count();
+            if crowd >= CROWD_SIZE && inventory.grenades > 0 && brain.grenade_cooldown.finished() {
+                actions.throw_grenade = true;
+                brain.grenade_cooldown.reset();
+            }
+        }
+    }
+}
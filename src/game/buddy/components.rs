@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+/// AI companion that follows `leader`, a player or the buddy in front of it
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Component)]
+pub struct Buddy {
+    pub leader: Entity,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Component)]
+pub struct BuddyTarget {
+    pub zombie: Option<Entity>,
+}
+
+/// Buddy ran out of health and waits for a player to stand next to it
+#[derive(Clone, Debug, Component)]
+pub struct Downed {
+    pub revive_timer: Timer,
+}
+
+impl Default for Downed {
+    fn default() -> Self {
+        Self {
+            revive_timer: Timer::from_seconds(3.0, TimerMode::Once),
+        }
+    }
+}
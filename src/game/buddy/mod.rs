@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+mod systems;
+pub mod components;
+
+use systems::*;
+
+use crate::{AppState, FixedGameplaySet, HostGameplaySet, SimulationState};
+
+pub struct BuddyPlugin;
+
+impl Plugin for BuddyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            spawn_buddy
+                .in_set(HostGameplaySet)
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+        )
+        .add_systems(
+            (
+                buddy_follow,
+                buddy_pick_target,
+                buddy_shoot.after(buddy_pick_target),
+                buddy_take_dmg,
+                buddy_down.after(buddy_take_dmg),
+                buddy_revive,
+            )
+            .in_set(FixedGameplaySet)
+            .in_schedule(CoreSchedule::FixedUpdate)
+        );
+    }
+}
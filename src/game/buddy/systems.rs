@@ -0,0 +1,256 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+use crate::{AppState, MouseLoc};
+use crate::components::{Ammo, ColliderBundle, FireRate, Health, StateScoped, Weapon};
+use crate::game::enemies::components::Zombie;
+use crate::game::player::components::Player;
+use crate::game::player::systems::spawn_bullet;
+use crate::graphics::*;
+
+const BUDDY_HEALTH: i32 = 6;
+const BUDDY_SPEED: f32 = 130.0;
+/// Distance a buddy keeps from whoever it follows
+const BUDDY_SPACING: f32 = 24.0;
+const BUDDY_SIGHT_RANGE: f32 = 160.0;
+const REVIVE_RADIUS: f32 = 20.0;
+
+pub fn spawn_buddy(
+    mut commands: Commands,
+    mouse_pos: Res<MouseLoc>,
+    input: Res<Input<KeyCode>>,
+    characters: Res<CharacterSheet>,
+    player_query: Query<Entity, With<Player>>,
+    buddy_query: Query<(Entity, &Buddy)>,
+) {
+    if !input.just_pressed(KeyCode::B) {
+        return;
+    }
+
+    // Join the end of the line: follow the buddy nobody follows yet, or the player
+    let last_buddy = buddy_query
+        .iter()
+        .map(|(entity, _)| entity)
+        .find(|entity| !buddy_query.iter().any(|(_, buddy)| buddy.leader == *entity));
+    let Some(leader) = last_buddy.or_else(|| player_query.iter().next()) else {
+        return;
+    };
+
+    let buddy = spawn_player_sprite(
+        &mut commands,
+        &characters,
+        Vec3::new(mouse_pos.loc.x, mouse_pos.loc.y, 0.0),
+    );
+    commands
+        .entity(buddy)
+        .remove::<FrameAnimation>()
+        .insert(ColliderBundle {
+            collider: Collider::cuboid(5.0, 5.0),
+            rigid_body: RigidBody::Dynamic,
+            friction: Friction {
+                coefficient: 0.0,
+                combine_rule: CoefficientCombineRule::Min,
+            },
+            rotation_constraints: LockedAxes::ROTATION_LOCKED,
+            ..Default::default()
+        })
+        .insert(characters.animations())
+        .insert(Weapon {
+            fire_rate: FireRate {
+                timer: Timer::from_seconds(0.4, TimerMode::Repeating),
+            },
+            mag_size: 12,
+            ammo: Ammo { bullets: 12 },
+            ..Default::default()
+        })
+        .insert(Health {
+            health_points: BUDDY_HEALTH,
+        })
+        .insert(Buddy { leader })
+        .insert(BuddyTarget::default())
+        .insert(ActiveEvents::COLLISION_EVENTS)
+        .insert(StateScoped(AppState::Game));
+}
+
+#[allow(clippy::type_complexity)]
+pub fn buddy_follow(
+    mut buddy_query: Query<(&mut Buddy, &Transform, &mut Velocity, &mut Animations), Without<Downed>>,
+    leader_query: Query<&Transform, Or<(With<Player>, With<Buddy>)>>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+) {
+    for (mut buddy, transform, mut velocity, mut anim) in buddy_query.iter_mut() {
+        // The leader died or despawned, fall back to the nearest player
+        if leader_query.get(buddy.leader).is_err() {
+            let nearest_player = player_query.iter().min_by(|(_, a), (_, b)| {
+                a.translation.distance_squared(transform.translation)
+                    .total_cmp(&b.translation.distance_squared(transform.translation))
+            });
+            match nearest_player {
+                Some((player, _)) => buddy.leader = player,
+                None => {
+                    velocity.linvel = Vec2::ZERO;
+                    continue;
+                }
+            }
+        }
+
+        let Ok(leader_transform) = leader_query.get(buddy.leader) else {
+            continue;
+        };
+        let to_leader = (leader_transform.translation - transform.translation).truncate();
+        if to_leader.length() > BUDDY_SPACING {
+            velocity.linvel = to_leader.normalize() * BUDDY_SPEED;
+            anim.current_animation = 0;
+        } else {
+            velocity.linvel = Vec2::ZERO;
+            anim.current_animation = 3;
+        }
+    }
+}
+
+pub fn buddy_pick_target(
+    mut buddy_query: Query<(Entity, &Transform, &mut BuddyTarget), Without<Downed>>,
+    zombie_query: Query<(Entity, &Transform, &Health), With<Zombie>>,
+    rapier_context: Res<RapierContext>,
+) {
+    for (buddy, transform, mut target) in buddy_query.iter_mut() {
+        let origin = transform.translation.truncate();
+        let mut zombies_in_range: Vec<(Entity, Vec2, f32)> = zombie_query
+            .iter()
+            .filter(|(_, _, health)| health.health_points > 0)
+            .map(|(zombie, zombie_transform, _)| {
+                let position = zombie_transform.translation.truncate();
+                (zombie, position, position.distance(origin))
+            })
+            .filter(|(_, _, distance)| *distance < BUDDY_SIGHT_RANGE)
+            .collect();
+        zombies_in_range.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        // A zombie is visible if it is the first solid thing a ray towards it hits
+        target.zombie = zombies_in_range
+            .into_iter()
+            .find(|(zombie, position, distance)| {
+                let filter = QueryFilter::new().exclude_sensors().exclude_rigid_body(buddy);
+                match rapier_context.cast_ray(origin, (*position - origin).normalize(), *distance, true, filter) {
+                    Some((hit, _)) => hit == *zombie,
+                    None => true,
+                }
+            })
+            .map(|(zombie, _, _)| zombie);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn buddy_shoot(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fixed_time: Res<FixedTime>,
+    mut buddy_query: Query<
+        (Entity, &Transform, &mut Weapon, &BuddyTarget, &mut TextureAtlasSprite, &mut Animations),
+        Without<Downed>,
+    >,
+    zombie_query: Query<&Transform, With<Zombie>>,
+) {
+    for (buddy, transform, mut weapon, target, mut sprite, mut anim) in buddy_query.iter_mut() {
+        if weapon.reloading {
+            weapon.reload_timer.reload_timer.tick(fixed_time.period);
+            if weapon.reload_timer.reload_timer.finished() {
+                weapon.ammo.bullets = weapon.mag_size;
+                weapon.reload_timer.reload_timer.reset();
+                weapon.reloading = false;
+            }
+            continue;
+        }
+
+        let Some(target_transform) = target.zombie.and_then(|zombie| zombie_query.get(zombie).ok()) else {
+            continue;
+        };
+
+        sprite.flip_x = target_transform.translation.x < transform.translation.x;
+        anim.current_animation = 2;
+
+        weapon.fire_rate.timer.tick(fixed_time.period);
+        if weapon.fire_rate.timer.finished() {
+            spawn_bullet(
+                &mut commands,
+                &asset_server,
+                buddy,
+                transform.translation,
+                target_transform.translation.truncate(),
+            );
+            weapon.ammo.bullets -= 1;
+            if weapon.ammo.bullets == 0 {
+                weapon.reloading = true;
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn buddy_take_dmg(
+    zombies: Query<Entity, With<Zombie>>,
+    mut buddy_query: Query<(Entity, &mut Health), (With<Buddy>, Without<Downed>)>,
+    mut buddy_collisions: EventReader<CollisionEvent>,
+) {
+    for col_event in buddy_collisions.iter() {
+        if let CollisionEvent::Started(e1, e2, _) = col_event.to_owned() {
+            for (buddy, mut health) in buddy_query.iter_mut() {
+                let other = if buddy == e1 {
+                    e2
+                } else if buddy == e2 {
+                    e1
+                } else {
+                    continue;
+                };
+                if zombies.contains(other) {
+                    health.health_points -= 1;
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn buddy_down(
+    mut commands: Commands,
+    mut buddy_query: Query<
+        (Entity, &Health, &mut Velocity, &mut TextureAtlasSprite, &mut Animations),
+        (With<Buddy>, Without<Downed>),
+    >,
+) {
+    for (buddy, health, mut velocity, mut sprite, mut anim) in buddy_query.iter_mut() {
+        if health.health_points <= 0 {
+            println!("Buddy is down");
+            velocity.linvel = Vec2::ZERO;
+            sprite.color = Color::GRAY;
+            anim.current_animation = 3;
+            commands.entity(buddy).insert(Downed::default());
+        }
+    }
+}
+
+pub fn buddy_revive(
+    mut commands: Commands,
+    fixed_time: Res<FixedTime>,
+    mut downed_query: Query<(Entity, &Transform, &mut Downed, &mut Health, &mut TextureAtlasSprite), With<Buddy>>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    for (buddy, transform, mut downed, mut health, mut sprite) in downed_query.iter_mut() {
+        let player_nearby = player_query
+            .iter()
+            .any(|player| player.translation.distance(transform.translation) < REVIVE_RADIUS);
+        if !player_nearby {
+            downed.revive_timer.reset();
+            continue;
+        }
+
+        downed.revive_timer.tick(fixed_time.period);
+        if downed.revive_timer.finished() {
+            println!("Buddy revived");
+            health.health_points = BUDDY_HEALTH / 2;
+            sprite.color = Color::WHITE;
+            commands.entity(buddy).remove::<Downed>();
+        }
+    }
+}
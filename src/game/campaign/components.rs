@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::manifest::CampaignManifest;
+use crate::components::{Health, Inventory, MaxHealth, Weapon};
+use crate::game::player::components::PlayerId;
+
+pub const CAMPAIGN_MANIFEST: &str = "main.campaign.ron";
+
+/// Where the run is in the campaign, and where the next load takes it
+#[derive(Resource, Clone, Debug, Default)]
+pub struct CampaignProgress {
+    pub manifest: Handle<CampaignManifest>,
+    /// Index into the manifest worlds
+    pub world: usize,
+    /// Identifier of the level to start in, the first level of the world if `None`
+    pub level: Option<String>,
+    /// The world for this load was spawned, waiting for its levels
+    pub world_spawned: bool,
+}
+
+/// What a player had when leaving a map
+#[derive(Clone, Debug)]
+pub struct PlayerLoadout {
+    pub health: Health,
+    pub max_health: MaxHealth,
+    pub weapon: Weapon,
+    pub inventory: Inventory,
+}
+
+/// Loadouts of the players that left through an exit, handed back when they spawn in the next map
+#[derive(Resource, Clone, Debug, Default)]
+pub struct CarryOver {
+    pub players: HashMap<PlayerId, PlayerLoadout>,
+}
@@ -0,0 +1,33 @@
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+/// Worlds of the campaign in the order they are played, loaded from a `.campaign.ron` file
+#[derive(Clone, Debug, Deserialize, TypeUuid)]
+#[uuid = "9d41f0a2-6c3e-4b7f-8e15-2a7d3c9b0f64"]
+pub struct CampaignManifest {
+    /// Paths of the `.ldtk` files
+    pub worlds: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct CampaignManifestLoader;
+
+impl AssetLoader for CampaignManifestLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let manifest: CampaignManifest = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(manifest));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["campaign.ron"]
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+mod systems;
+pub mod components;
+pub mod manifest;
+
+use components::{CampaignProgress, CarryOver};
+use manifest::{CampaignManifest, CampaignManifestLoader};
+use systems::*;
+
+use crate::{AppState, HostGameplaySet, SimulationState};
+
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<CampaignManifest>()
+            .init_asset_loader::<CampaignManifestLoader>()
+            .init_resource::<CampaignProgress>()
+            .init_resource::<CarryOver>()
+            .add_startup_system(load_campaign_manifest)
+            .add_system(start_campaign.in_schedule(OnExit(AppState::MainMenu)))
+            .add_system(start_campaign.in_schedule(OnExit(AppState::GameOver)))
+            .add_system(store_carry_over.in_schedule(OnEnter(AppState::Loading)))
+            .add_systems(
+                (
+                    spawn_campaign_world,
+                    finish_loading.before(spawn_campaign_world),
+                )
+                .in_set(OnUpdate(AppState::Loading))
+            )
+            .add_systems(
+                (
+                    trigger_level_exits.in_set(HostGameplaySet),
+                    apply_carry_over,
+                )
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            );
+    }
+}
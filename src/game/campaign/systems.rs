@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use super::components::*;
+use super::manifest::CampaignManifest;
+use crate::AppState;
+use crate::components::{Health, Inventory, MaxHealth, StateScoped, Weapon};
+use crate::game::buddy::components::Buddy;
+use crate::game::objectives::components::LevelExit;
+use crate::game::player::components::{Player, PlayerId};
+
+pub fn load_campaign_manifest(
+    asset_server: Res<AssetServer>,
+    mut progress: ResMut<CampaignProgress>,
+) {
+    progress.manifest = asset_server.load(CAMPAIGN_MANIFEST);
+}
+
+/// Leaving the main menu or the Game Over screen starts a new run from the first world
+pub fn start_campaign(
+    mut progress: ResMut<CampaignProgress>,
+    mut carry_over: ResMut<CarryOver>,
+) {
+    progress.world = 0;
+    progress.level = None;
+    carry_over.players.clear();
+}
+
+/// Keeps the loadouts of the players and removes them, they spawn again once the next map is in
+#[allow(clippy::type_complexity)]
+pub fn store_carry_over(
+    mut commands: Commands,
+    mut progress: ResMut<CampaignProgress>,
+    mut carry_over: ResMut<CarryOver>,
+    player_query: Query<(Entity, &PlayerId, &Health, &MaxHealth, &Weapon, &Inventory), With<Player>>,
+) {
+    progress.world_spawned = false;
+    for (player, player_id, health, max_health, weapon, inventory) in player_query.iter() {
+        let mut inventory = inventory.clone();
+        // Keys only open doors of the map they were found on
+        inventory.keys.clear();
+        carry_over.players.insert(*player_id, PlayerLoadout {
+            health: health.clone(),
+            max_health: max_health.clone(),
+            weapon: weapon.clone(),
+            inventory,
+        });
+        commands.entity(player).despawn_recursive();
+    }
+}
+
+/// Replaces the current world with the one the campaign is at, once the manifest is loaded
+pub fn spawn_campaign_world(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut progress: ResMut<CampaignProgress>,
+    manifests: Res<Assets<CampaignManifest>>,
+    mut level_selection: ResMut<LevelSelection>,
+    world_query: Query<Entity, With<Handle<LdtkAsset>>>,
+) {
+    if progress.world_spawned {
+        return;
+    }
+    let Some(manifest) = manifests.get(&progress.manifest) else {
+        return;
+    };
+    let Some(world) = manifest.worlds.get(progress.world) else {
+        println!("The campaign has no world {}", progress.world);
+        return;
+    };
+
+    for old_world in world_query.iter() {
+        commands.entity(old_world).despawn_recursive();
+    }
+    println!("Loading {}", world);
+    // Lives through the loading screen, goes away once the game is left for anything
+    commands.spawn((
+        LdtkWorldBundle {
+            ldtk_handle: asset_server.load(world.as_str()),
+            ..Default::default()
+        },
+        StateScoped(AppState::Game),
+    ));
+    *level_selection = match &progress.level {
+        Some(level) => LevelSelection::Identifier(level.clone()),
+        None => LevelSelection::Index(0),
+    };
+    progress.world_spawned = true;
+}
+
+/// Starts the game once a level of the new world is spawned and in place.
+/// Runs before `spawn_campaign_world` so level events of the old world are read and dropped first.
+pub fn finish_loading(
+    progress: Res<CampaignProgress>,
+    mut level_events: EventReader<LevelEvent>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    let transformed = level_events
+        .iter()
+        .any(|event| matches!(event, LevelEvent::Transformed(_)));
+    if progress.world_spawned && transformed {
+        app_state_next_state.set(AppState::Game);
+    }
+}
+
+pub fn trigger_level_exits(
+    mut progress: ResMut<CampaignProgress>,
+    manifests: Res<Assets<CampaignManifest>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    exit_query: Query<(&GlobalTransform, &LevelExit)>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+) {
+    let Some((_, exit)) = exit_query.iter().find(|(exit_transform, exit)| {
+        player_query
+            .iter()
+            .any(|player| exit.contains(exit_transform, player.translation().truncate()))
+    }) else {
+        return;
+    };
+    let Some(manifest) = manifests.get(&progress.manifest) else {
+        return;
+    };
+
+    // An exit goes to the `World` it names, to another `Level` of this world, or on to the next world
+    let world = match &exit.world {
+        Some(path) => match manifest.worlds.iter().position(|world| world == path) {
+            Some(index) => index,
+            None => {
+                println!("{} is not part of the campaign", path);
+                return;
+            }
+        },
+        None if exit.level.is_some() => progress.world,
+        None => progress.world + 1,
+    };
+    if world >= manifest.worlds.len() {
+        println!("Campaign complete");
+        app_state_next_state.set(AppState::GameOver);
+        return;
+    }
+
+    progress.world = world;
+    progress.level = exit.level.clone();
+    app_state_next_state.set(AppState::Loading);
+}
+
+#[allow(clippy::type_complexity)]
+pub fn apply_carry_over(
+    mut carry_over: ResMut<CarryOver>,
+    mut player_query: Query<
+        (&PlayerId, &Transform, &mut Health, &mut MaxHealth, &mut Weapon, &mut Inventory),
+        Added<Player>
+    >,
+    mut buddy_query: Query<&mut Transform, (With<Buddy>, Without<Player>)>,
+) {
+    for (player_id, transform, mut health, mut max_health, mut weapon, mut inventory) in player_query.iter_mut() {
+        let Some(loadout) = carry_over.players.remove(player_id) else {
+            continue;
+        };
+        *health = loadout.health;
+        *max_health = loadout.max_health;
+        *weapon = loadout.weapon;
+        *inventory = loadout.inventory;
+        // Buddies aren't part of the world, bring them along to the new map
+        for mut buddy_transform in buddy_query.iter_mut() {
+            buddy_transform.translation = transform.translation;
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+/// Area placed in LDtk, players respawn at the last one somebody walked into
+#[derive(Copy, Clone, PartialEq, Debug, Default, Component)]
+pub struct Checkpoint {
+    pub size: Vec2,
+    /// The checkpoint players respawn at right now
+    pub active: bool,
+}
+
+impl Checkpoint {
+    pub fn contains(&self, checkpoint_transform: &GlobalTransform, point: Vec2) -> bool {
+        let offset = (point - checkpoint_transform.translation().truncate()).abs();
+        offset.x <= self.size.x / 2.0 && offset.y <= self.size.y / 2.0
+    }
+}
+
+impl From<&EntityInstance> for Checkpoint {
+    fn from(entity_instance: &EntityInstance) -> Checkpoint {
+        Checkpoint {
+            size: Vec2::new(entity_instance.width as f32, entity_instance.height as f32),
+            active: false,
+        }
+    }
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct CheckpointBundle {
+    #[bundle]
+    pub sprite_bundle: SpriteBundle,
+    #[from_entity_instance]
+    pub checkpoint: Checkpoint,
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+mod systems;
+pub mod components;
+
+use components::CheckpointBundle;
+use systems::*;
+
+use crate::{AppState, SimulationState};
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_entity::<CheckpointBundle>("Checkpoint")
+            .add_system(setup_checkpoints)
+            .add_systems(
+                (
+                    touch_checkpoints,
+                    update_checkpoint_colors.after(touch_checkpoints),
+                )
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            );
+    }
+}
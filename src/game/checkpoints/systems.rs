@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+
+use super::components::*;
+use crate::game::player::components::Player;
+use crate::game::player::resources::PlayerSpawns;
+
+fn checkpoint_color(checkpoint: &Checkpoint) -> Color {
+    if checkpoint.active {
+        Color::rgba(0.31, 0.64, 0.88, 0.6)
+    } else {
+        Color::rgba(0.31, 0.64, 0.88, 0.2)
+    }
+}
+
+pub fn setup_checkpoints(
+    mut commands: Commands,
+    checkpoint_query: Query<(Entity, &Checkpoint), Added<Checkpoint>>,
+) {
+    for (checkpoint_entity, checkpoint) in checkpoint_query.iter() {
+        commands
+            .entity(checkpoint_entity)
+            .insert(Sprite {
+                color: checkpoint_color(checkpoint),
+                custom_size: Some(checkpoint.size),
+                ..Default::default()
+            })
+            .insert(DEFAULT_IMAGE_HANDLE.typed::<Image>());
+    }
+}
+
+/// Walking into a checkpoint moves the spawn point of every player slot there
+pub fn touch_checkpoints(
+    mut player_spawns: ResMut<PlayerSpawns>,
+    mut checkpoint_query: Query<(Entity, &GlobalTransform, &mut Checkpoint)>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+) {
+    let touched = checkpoint_query
+        .iter()
+        .filter(|(_, _, checkpoint)| !checkpoint.active)
+        .find(|(_, checkpoint_transform, checkpoint)| {
+            player_query
+                .iter()
+                .any(|player| checkpoint.contains(checkpoint_transform, player.translation().truncate()))
+        })
+        .map(|(checkpoint_entity, checkpoint_transform, _)| (checkpoint_entity, checkpoint_transform.translation()));
+    let Some((touched, position)) = touched else {
+        return;
+    };
+
+    println!("Checkpoint reached");
+    for (checkpoint_entity, _, mut checkpoint) in checkpoint_query.iter_mut() {
+        checkpoint.active = checkpoint_entity == touched;
+    }
+    // Players respawning together are lined up next to each other
+    let mut slots: Vec<_> = player_spawns.points.keys().copied().collect();
+    slots.sort_by_key(|player_id| player_id.0);
+    for (index, player_id) in slots.into_iter().enumerate() {
+        if let Some(spawn_point) = player_spawns.points.get_mut(&player_id) {
+            spawn_point.position = Vec3::new(position.x + index as f32 * 16.0, position.y, 0.0);
+        }
+    }
+}
+
+pub fn update_checkpoint_colors(
+    mut checkpoint_query: Query<(&Checkpoint, &mut Sprite), Changed<Checkpoint>>,
+) {
+    for (checkpoint, mut sprite) in checkpoint_query.iter_mut() {
+        sprite.color = checkpoint_color(checkpoint);
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::fields::string_field;
+
+/// Seconds a single zombie needs to bash a door down
+pub const DOOR_HEALTH: f32 = 6.0;
+
+#[derive(Clone, Debug, Component)]
+pub struct Door {
+    pub open: bool,
+    /// Key item needed to unlock the door, `None` for unlocked doors
+    pub key: Option<String>,
+    pub health: f32,
+    pub broken: bool,
+}
+
+impl Default for Door {
+    fn default() -> Self {
+        Self {
+            open: false,
+            key: None,
+            health: DOOR_HEALTH,
+            broken: false,
+        }
+    }
+}
+
+impl From<&EntityInstance> for Door {
+    fn from(entity_instance: &EntityInstance) -> Door {
+        Door {
+            key: string_field(&entity_instance.field_instances, "Key"),
+            ..Default::default()
+        }
+    }
+}
+
+/// Door painted in the `Doors` IntGrid layer
+#[derive(Clone, Debug, Default, Bundle, LdtkIntCell)]
+pub struct DoorCellBundle {
+    pub door: Door,
+}
+
+/// Door placed as an LDtk entity, these can be locked with the `Key` field
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct DoorBundle {
+    #[bundle]
+    pub sprite_bundle: SpriteBundle,
+    #[from_entity_instance]
+    pub door: Door,
+}
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+mod systems;
+pub mod components;
+
+use components::{DoorBundle, DoorCellBundle};
+use systems::*;
+
+use crate::{AppState, HostGameplaySet, SimulationState};
+use crate::game::interaction::systems::send_interact_events;
+
+pub struct DoorPlugin;
+
+impl Plugin for DoorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_int_cell_for_layer::<DoorCellBundle>("Doors", 1)
+            .register_ldtk_entity::<DoorBundle>("Door")
+            .add_system(setup_doors)
+            .add_systems(
+                (
+                    toggle_doors.after(send_interact_events),
+                    bash_doors,
+                    update_door_colliders.after(toggle_doors).after(bash_doors),
+                )
+                .in_set(HostGameplaySet)
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            );
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+use crate::components::Inventory;
+use crate::game::enemies::components::Zombie;
+use crate::game::interaction::components::{InteractEvent, Interactable};
+use crate::game::player::components::Player;
+
+/// How close a player has to stand to use a door
+const DOOR_REACH: f32 = 24.0;
+/// How close a zombie has to be to bash a door
+const BASH_REACH: f32 = 22.0;
+
+fn door_color(door: &Door) -> Color {
+    if door.broken {
+        Color::rgba(0.56, 0.34, 0.23, 0.1)
+    } else if door.open {
+        Color::rgba(0.56, 0.34, 0.23, 0.3)
+    } else if door.key.is_some() {
+        Color::rgb(0.45, 0.16, 0.12)
+    } else {
+        Color::rgb(0.56, 0.34, 0.23)
+    }
+}
+
+fn door_prompt(door: &Door) -> String {
+    match (&door.key, door.open) {
+        (Some(key), _) => format!("Unlock door ({} key)", key),
+        (None, true) => "Close door".to_string(),
+        (None, false) => "Open door".to_string(),
+    }
+}
+
+pub fn setup_doors(
+    mut commands: Commands,
+    door_query: Query<(Entity, &Door), Added<Door>>,
+) {
+    for (door_entity, door) in door_query.iter() {
+        commands
+            .entity(door_entity)
+            .insert(Collider::cuboid(8.0, 8.0))
+            .insert(RigidBody::Fixed)
+            .insert(Sprite {
+                color: door_color(door),
+                custom_size: Some(Vec2::splat(16.0)),
+                ..Default::default()
+            })
+            .insert(DEFAULT_IMAGE_HANDLE.typed::<Image>())
+            .insert(Interactable::new(DOOR_REACH, door_prompt(door)));
+    }
+}
+
+/// Doors touching each other open and close together, so a wide door made of several cells acts as one
+fn connected_doors(doors: &[(Entity, Vec2)], start: Entity) -> Vec<Entity> {
+    let mut group = vec![start];
+    let mut index = 0;
+    while index < group.len() {
+        let Some(&(_, position)) = doors.iter().find(|(entity, _)| *entity == group[index]) else {
+            break;
+        };
+        for (entity, other) in doors {
+            if !group.contains(entity) && position.distance(*other) <= 16.5 {
+                group.push(*entity);
+            }
+        }
+        index += 1;
+    }
+    group
+}
+
+pub fn toggle_doors(
+    mut interact_events: EventReader<InteractEvent>,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+    mut door_query: Query<(Entity, &GlobalTransform, &mut Door)>,
+) {
+    for event in interact_events.iter() {
+        let Ok((_, _, target_door)) = door_query.get(event.target) else {
+            continue;
+        };
+        let open = !target_door.open;
+
+        if let Some(key) = target_door.key.clone() {
+            let Ok(mut inventory) = player_query.get_mut(event.player) else {
+                continue;
+            };
+            if !inventory.keys.contains(&key) {
+                println!("The door is locked, it needs the {} key", key);
+                continue;
+            }
+            inventory.keys.retain(|held| *held != key);
+            println!("Unlocked the door with the {} key", key);
+        }
+
+        let doors: Vec<(Entity, Vec2)> = door_query
+            .iter()
+            .filter(|(_, _, door)| !door.broken)
+            .map(|(entity, transform, _)| (entity, transform.translation().truncate()))
+            .collect();
+        for entity in connected_doors(&doors, event.target) {
+            if let Ok((_, _, mut door)) = door_query.get_mut(entity) {
+                door.key = None;
+                door.open = open;
+            }
+        }
+    }
+}
+
+pub fn bash_doors(
+    time: Res<Time>,
+    zombie_query: Query<&GlobalTransform, With<Zombie>>,
+    mut door_query: Query<(&GlobalTransform, &mut Door)>,
+) {
+    for (door_transform, mut door) in door_query.iter_mut() {
+        if door.open || door.broken {
+            continue;
+        }
+
+        let bashing = zombie_query
+            .iter()
+            .filter(|zombie| zombie.translation().distance(door_transform.translation()) < BASH_REACH)
+            .count();
+        if bashing == 0 {
+            continue;
+        }
+
+        door.health -= time.delta_seconds() * bashing as f32;
+        if door.health <= 0.0 {
+            println!("Zombies broke a door down");
+            door.broken = true;
+            door.open = true;
+        }
+    }
+}
+
+pub fn update_door_colliders(
+    mut commands: Commands,
+    mut door_query: Query<(Entity, &Door, &mut Sprite, Option<&mut Interactable>), Changed<Door>>,
+) {
+    for (door_entity, door, mut sprite, interactable) in door_query.iter_mut() {
+        sprite.color = door_color(door);
+        if door.broken {
+            commands.entity(door_entity).remove::<Interactable>();
+        } else if let Some(mut interactable) = interactable {
+            interactable.prompt = door_prompt(door);
+        }
+        if door.open {
+            commands.entity(door_entity).insert(ColliderDisabled);
+        } else {
+            commands.entity(door_entity).remove::<ColliderDisabled>();
+        }
+    }
+}
@@ -1,4 +1,8 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Zombies go after the nearest player or follower closer than this
+pub const ZOMBIE_CHASE_RANGE: f32 = 150.0;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
 pub struct Enemy;
@@ -6,3 +10,26 @@ pub struct Enemy;
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
 pub struct Zombie;
 
+/// What can hurt a zombie
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum DamageSource {
+    #[default]
+    Gun,
+    Grenade,
+}
+
+/// What hurt a zombie last, it gets the credit for the kill
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
+pub struct LastHit {
+    pub source: DamageSource,
+}
+
+/// Sent when a zombie runs out of health, right before it despawns
+#[derive(Clone, PartialEq, Debug)]
+pub struct ZombieKilled {
+    pub position: Vec2,
+    /// Iid of the zombie's LDtk entity, `None` for zombies the map didn't place
+    pub iid: Option<String>,
+    pub source: DamageSource,
+}
+
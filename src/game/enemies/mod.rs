@@ -3,21 +3,23 @@ use bevy::prelude::*;
 mod systems;
 pub mod components;
 
+use components::ZombieKilled;
 use systems::*;
 
-use crate::AppState;
+use crate::{AppState, FixedGameplaySet};
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.add_event::<ZombieKilled>()
+        .add_systems(
             (
                 zombie_movement, 
                 despawn_zombie
             )
-            .in_set(OnUpdate(AppState::Game))
-            //.in_set(OnUpdate(SimulationState::Running))
+            .in_set(FixedGameplaySet)
+            .in_schedule(CoreSchedule::FixedUpdate)
         )
         .add_systems(
             (
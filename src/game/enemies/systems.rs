@@ -1,35 +1,63 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
 
 use crate::game::player::components::Player;
+use crate::resources::LoadedLevels;
+use crate::game::rescue::components::Follower;
 use crate::components::Health;
 use super::components::*;
-use crate::AppState;
 
 pub fn zombie_movement(
     mut zombie_query: Query<(&mut Velocity, &Transform), With<Zombie>>,
-    player_query: Query<&Transform, With<Player>>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    survivor_query: Query<&Transform, With<Follower>>,
+    loaded_levels: Res<LoadedLevels>,
 ) {
-    if let Ok(player_pos) = player_query.get_single() {
-        for (mut zombie_vel, zombie_pos) in zombie_query.iter_mut() {
-            if zombie_pos.translation.distance(player_pos.translation) < 150.0 {
-                zombie_vel.linvel = (player_pos.translation - zombie_pos.translation)
+    for (mut zombie_vel, zombie_pos) in zombie_query.iter_mut() {
+        // Zombies are worldly and outlive their level, but its walls don't.
+        // One left behind in an unloaded level waits there until the level is back.
+        if !loaded_levels.contains(zombie_pos.translation.truncate()) {
+            zombie_vel.linvel = Vec2::ZERO;
+            continue;
+        }
+
+        let nearest_target = player_query
+            .iter()
+            .filter(|(_, health)| health.health_points > 0)
+            .map(|(player_pos, _)| player_pos.translation)
+            // Survivors following a player are fair game too
+            .chain(survivor_query.iter().map(|survivor_pos| survivor_pos.translation))
+            .min_by(|a, b| {
+                a.distance_squared(zombie_pos.translation)
+                    .total_cmp(&b.distance_squared(zombie_pos.translation))
+            });
+
+        match nearest_target {
+            Some(player_pos) if zombie_pos.translation.distance(player_pos) < ZOMBIE_CHASE_RANGE => {
+                zombie_vel.linvel = (player_pos - zombie_pos.translation)
                     .truncate()
                     .normalize()
                     * 80.0;
-            } else {
-                zombie_vel.linvel = Vec2::ZERO;
             }
+            _ => zombie_vel.linvel = Vec2::ZERO,
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn despawn_zombie(
     mut commands: Commands, 
-    zombie_query: Query<(&mut Health, Entity), With<Zombie>>,
+    zombie_query: Query<(&Health, Entity, &Transform, Option<&EntityInstance>, &LastHit), With<Zombie>>,
+    mut killed_events: EventWriter<ZombieKilled>,
 ) {
-    for (health, zombie) in zombie_query.iter() {
+    for (health, zombie, transform, entity_instance, last_hit) in zombie_query.iter() {
         if health.health_points <= 0 {
+            killed_events.send(ZombieKilled {
+                position: transform.translation.truncate(),
+                iid: entity_instance.map(|entity_instance| entity_instance.iid.clone()),
+                source: last_hit.source,
+            });
             commands.entity(zombie).despawn();
         }
     }
@@ -37,9 +65,9 @@ pub fn despawn_zombie(
 
 pub fn despawn_zombies(
     mut commands: Commands,
-    zombie_query: Query<(&mut Health, Entity), With<Zombie>>,
+    zombie_query: Query<Entity, With<Zombie>>,
 ) {
-    for (health, zombie) in zombie_query.iter(){
+    for zombie in zombie_query.iter(){
         commands.entity(zombie).despawn();
     }
 }
\ No newline at end of file
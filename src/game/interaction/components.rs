@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+/// Something a player can use by pressing interact while standing within `radius`
+#[derive(Clone, PartialEq, Debug, Default, Component)]
+pub struct Interactable {
+    pub radius: f32,
+    /// Shown after the interact button, e.g. "Open door"
+    pub prompt: String,
+}
+
+impl Interactable {
+    pub fn new(radius: f32, prompt: impl Into<String>) -> Self {
+        Self {
+            radius,
+            prompt: prompt.into(),
+        }
+    }
+}
+
+/// The nearest `Interactable` in reach of a player
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
+pub struct InteractFocus {
+    pub target: Option<Entity>,
+}
+
+/// World space prompt text following the focus of `player`
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Component)]
+pub struct InteractPrompt {
+    pub player: Entity,
+}
+
+/// Sent when `player` presses interact while focusing `target`
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct InteractEvent {
+    pub player: Entity,
+    pub target: Entity,
+}
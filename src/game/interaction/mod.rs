@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+pub(crate) mod systems;
+pub mod components;
+
+use components::InteractEvent;
+use systems::*;
+
+use crate::{AppState, SimulationState};
+use crate::game::npcs::systems::not_in_dialogue;
+use crate::game::player::input::read_player_input;
+
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<InteractEvent>()
+            .add_systems(
+                (
+                    setup_interactors,
+                    find_interact_focus.after(setup_interactors),
+                    send_interact_events
+                        .run_if(not_in_dialogue)
+                        .after(find_interact_focus)
+                        .after(read_player_input),
+                    update_interact_prompts.after(find_interact_focus),
+                )
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            );
+    }
+}
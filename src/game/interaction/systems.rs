@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use super::components::*;
+use crate::AppState;
+use crate::components::StateScoped;
+use crate::game::player::components::{InputDevice, Player, PlayerActions};
+
+/// How far above the interactable the prompt is drawn
+const PROMPT_OFFSET: f32 = 14.0;
+const PROMPT_FONT_SIZE: f32 = 8.0;
+
+pub fn setup_interactors(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    player_query: Query<Entity, Added<Player>>,
+) {
+    for player in player_query.iter() {
+        commands.entity(player).insert(InteractFocus::default());
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: PROMPT_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                ),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            InteractPrompt { player },
+            StateScoped(AppState::Game),
+        ));
+    }
+}
+
+pub fn find_interact_focus(
+    mut player_query: Query<(&GlobalTransform, &mut InteractFocus), With<Player>>,
+    interactable_query: Query<(Entity, &GlobalTransform, &Interactable)>,
+) {
+    for (player_transform, mut focus) in player_query.iter_mut() {
+        let player_position = player_transform.translation().truncate();
+        let target = interactable_query
+            .iter()
+            .map(|(entity, transform, interactable)| {
+                let distance = transform.translation().truncate().distance(player_position);
+                (entity, distance, interactable.radius)
+            })
+            .filter(|(_, distance, radius)| distance < radius)
+            .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+            .map(|(entity, _, _)| entity);
+
+        if focus.target != target {
+            focus.target = target;
+        }
+    }
+}
+
+pub fn send_interact_events(
+    player_query: Query<(Entity, &PlayerActions, &InteractFocus), With<Player>>,
+    mut interact_events: EventWriter<InteractEvent>,
+) {
+    for (player, actions, focus) in player_query.iter() {
+        if !actions.interact {
+            continue;
+        }
+        if let Some(target) = focus.target {
+            interact_events.send(InteractEvent { player, target });
+        }
+    }
+}
+
+pub fn update_interact_prompts(
+    mut commands: Commands,
+    player_query: Query<(&InteractFocus, &InputDevice), With<Player>>,
+    interactable_query: Query<(&GlobalTransform, &Interactable)>,
+    mut prompt_query: Query<(Entity, &InteractPrompt, &mut Text, &mut Transform, &mut Visibility)>,
+) {
+    for (prompt_entity, prompt, mut text, mut transform, mut visibility) in prompt_query.iter_mut() {
+        let Ok((focus, device)) = player_query.get(prompt.player) else {
+            commands.entity(prompt_entity).despawn_recursive();
+            continue;
+        };
+        let Some((target_transform, interactable)) = focus
+            .target
+            .and_then(|target| interactable_query.get(target).ok()) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let button = match device {
+            InputDevice::KeyboardMouse | InputDevice::Bot | InputDevice::Remote(_) => "E",
+            InputDevice::Gamepad(_) => "A",
+        };
+        let value = format!("{} — {}", button, interactable.prompt);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+        transform.translation = target_transform.translation() + Vec3::new(0.0, PROMPT_OFFSET, 10.0);
+        *visibility = Visibility::Visible;
+    }
+}
@@ -1,5 +1,14 @@
-use bevy::prelude::*;
-
 pub mod player;
+pub mod campaign;
+pub mod checkpoints;
+pub mod buddy;
+pub mod bot;
+pub mod pickups;
+pub mod doors;
+pub mod interaction;
+pub mod npcs;
+pub mod objectives;
+pub mod rescue;
+pub mod stats;
 pub mod enemies;
 pub mod ui;
\ No newline at end of file
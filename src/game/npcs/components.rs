@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use super::dialogue::{Dialogue, DialogueAction};
+use crate::components::ColliderBundle;
+use crate::fields::*;
+
+/// How close a player has to stand to talk to an npc
+pub const NPC_REACH: f32 = 24.0;
+
+/// Survivor placed in LDtk, read from the `Name` and `Dialogue` fields
+#[derive(Clone, PartialEq, Debug, Default, Component)]
+pub struct Npc {
+    pub name: String,
+    /// Path of the `.dialogue.ron` asset
+    pub dialogue: String,
+}
+
+impl From<&EntityInstance> for Npc {
+    fn from(entity_instance: &EntityInstance) -> Npc {
+        let fields = &entity_instance.field_instances;
+        Npc {
+            name: string_field(fields, "Name").unwrap_or_else(|| "Survivor".to_string()),
+            dialogue: string_field(fields, "Dialogue")
+                .unwrap_or_else(|| "dialogue/survivor.dialogue.ron".to_string()),
+        }
+    }
+}
+
+/// Kept on the npc so its dialogue is loaded before anyone talks to it
+#[derive(Clone, Debug, Default, Component)]
+pub struct NpcDialogue {
+    pub handle: Handle<Dialogue>,
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct NpcBundle {
+    #[sprite_sheet_bundle("NPC/npc x1.png", 32.0, 32.0, 6, 3, 0.0, 0.0, 0)]
+    #[bundle]
+    pub sprite_sheet_bundle: SpriteSheetBundle,
+    #[from_entity_instance]
+    #[bundle]
+    pub collider_bundle: ColliderBundle,
+    #[from_entity_instance]
+    pub npc: Npc,
+}
+
+#[derive(Clone, Debug)]
+pub struct ActiveDialogue {
+    pub npc: Entity,
+    pub player: Entity,
+    pub dialogue: Handle<Dialogue>,
+    pub node: String,
+    /// Highlighted choice
+    pub selected: usize,
+}
+
+/// The conversation going on right now, only one player can talk at a time
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DialogueState {
+    pub active: Option<ActiveDialogue>,
+}
+
+/// Sent for every action of a picked dialogue choice
+#[derive(Clone, PartialEq, Debug)]
+pub struct DialogueEvent {
+    pub player: Entity,
+    pub npc: Entity,
+    pub action: DialogueAction,
+}
+
+/// A dialogue choice clicked with the mouse
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DialogueChoiceEvent {
+    pub index: usize,
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+use crate::game::objectives::components::ObjectiveKind;
+use crate::game::pickups::components::PickupKind;
+
+/// Dialogue tree loaded from a `.dialogue.ron` file
+#[derive(Clone, Debug, Deserialize, TypeUuid)]
+#[uuid = "3c2b4a1e-8f0d-4a5b-9a57-1f6c0f3e2d41"]
+pub struct Dialogue {
+    /// Node the conversation starts at
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DialogueNode {
+    /// Defaults to the name of the npc
+    #[serde(default)]
+    pub speaker: Option<String>,
+    pub text: String,
+    /// With no choices the conversation ends when the player continues
+    #[serde(default)]
+    pub choices: Vec<DialogueChoice>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DialogueChoice {
+    pub text: String,
+    /// Node to go to, `None` ends the conversation
+    #[serde(default)]
+    pub next: Option<String>,
+    #[serde(default)]
+    pub actions: Vec<DialogueAction>,
+}
+
+/// Happens when a choice is picked, sent out as a `DialogueEvent`
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub enum DialogueAction {
+    Give(PickupKind, i32),
+    GiveKey(String),
+    /// Adds an objective with this goal to the level the players are in
+    StartObjective(ObjectiveKind, f32),
+    /// The npc starts following the player, see `rescue`
+    Follow,
+}
+
+#[derive(Default)]
+pub struct DialogueLoader;
+
+impl AssetLoader for DialogueLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let dialogue: Dialogue = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(dialogue));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dialogue.ron"]
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+pub(crate) mod systems;
+pub mod components;
+pub mod dialogue;
+
+use components::*;
+use dialogue::{Dialogue, DialogueLoader};
+use systems::*;
+
+use crate::{AppState, SimulationState};
+use crate::game::interaction::systems::send_interact_events;
+
+pub struct NpcPlugin;
+
+impl Plugin for NpcPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Dialogue>()
+            .init_asset_loader::<DialogueLoader>()
+            .init_resource::<DialogueState>()
+            .add_event::<DialogueEvent>()
+            .add_event::<DialogueChoiceEvent>()
+            .register_ldtk_entity::<NpcBundle>("Npc")
+            .add_system(setup_npcs)
+            .add_systems(
+                (
+                    advance_dialogue.after(send_interact_events),
+                    start_dialogue.after(advance_dialogue),
+                    npc_talk_animation.after(start_dialogue),
+                    give_dialogue_items.after(advance_dialogue),
+                )
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            )
+            .add_system(end_dialogue.in_schedule(OnExit(AppState::Game)));
+    }
+}
@@ -0,0 +1,246 @@
+use bevy::prelude::*;
+
+use super::components::*;
+use super::dialogue::{Dialogue, DialogueAction};
+use crate::components::{Health, Inventory, MaxHealth, Weapon};
+use crate::game::interaction::components::{InteractEvent, Interactable};
+use crate::game::objectives::components::{MissionTracker, Objective};
+use crate::game::pickups::components::{Pickup, PickupKind};
+use crate::game::pickups::systems::apply_pickup;
+use crate::game::player::components::{Player, PlayerActions};
+use crate::graphics::{Animations, FrameAnimation};
+
+/// Idle and talk animations, in that order, for the 6x3 npc sheets
+fn npc_animations() -> Animations {
+    Animations {
+        animations: vec![
+            FrameAnimation {
+                timer: Timer::from_seconds(0.3, TimerMode::Repeating),
+                frames: vec![0, 1, 2, 3, 4],
+                current_frame: 0,
+            },
+            FrameAnimation {
+                timer: Timer::from_seconds(0.25, TimerMode::Repeating),
+                frames: vec![12, 13],
+                current_frame: 0,
+            },
+        ],
+        current_animation: 0,
+    }
+}
+
+fn npc_prompt(npc: &Npc) -> Interactable {
+    Interactable::new(NPC_REACH, format!("Talk to {}", npc.name))
+}
+
+pub fn setup_npcs(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    npc_query: Query<(Entity, &Npc), Added<Npc>>,
+) {
+    for (npc_entity, npc) in npc_query.iter() {
+        commands.entity(npc_entity).insert((
+            npc_prompt(npc),
+            NpcDialogue {
+                handle: asset_server.load(npc.dialogue.as_str()),
+            },
+            npc_animations(),
+        ));
+    }
+}
+
+pub fn start_dialogue(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    mut dialogue_state: ResMut<DialogueState>,
+    dialogues: Res<Assets<Dialogue>>,
+    npc_query: Query<(&Npc, &NpcDialogue)>,
+) {
+    for event in interact_events.iter() {
+        if dialogue_state.active.is_some() {
+            continue;
+        }
+        let Ok((npc, npc_dialogue)) = npc_query.get(event.target) else {
+            continue;
+        };
+        let Some(dialogue) = dialogues.get(&npc_dialogue.handle) else {
+            println!("{} has nothing to say yet, dialogue {} is not loaded", npc.name, npc.dialogue);
+            continue;
+        };
+
+        dialogue_state.active = Some(ActiveDialogue {
+            npc: event.target,
+            player: event.player,
+            dialogue: npc_dialogue.handle.clone(),
+            node: dialogue.start.clone(),
+            selected: 0,
+        });
+        // Nobody else can start talking to the npc, and the interact prompt gets out of the way
+        commands.entity(event.target).remove::<Interactable>();
+    }
+}
+
+/// Picking a dialogue choice shouldn't also fire a gun or open a door
+pub fn not_in_dialogue(dialogue_state: Res<DialogueState>) -> bool {
+    dialogue_state.active.is_none()
+}
+
+enum DialogueStep {
+    Stay,
+    Select(usize),
+    Goto(String),
+    End,
+}
+
+pub fn advance_dialogue(
+    mut commands: Commands,
+    mut dialogue_state: ResMut<DialogueState>,
+    mut choice_events: EventReader<DialogueChoiceEvent>,
+    mut dialogue_events: EventWriter<DialogueEvent>,
+    dialogues: Res<Assets<Dialogue>>,
+    player_query: Query<(&GlobalTransform, &PlayerActions), With<Player>>,
+    npc_query: Query<(&GlobalTransform, &Npc)>,
+) {
+    let Some(active) = dialogue_state.active.as_ref() else {
+        choice_events.clear();
+        return;
+    };
+
+    let player = player_query.get(active.player).ok();
+    let npc = npc_query.get(active.npc).ok();
+    let node = dialogues
+        .get(&active.dialogue)
+        .and_then(|dialogue| dialogue.nodes.get(&active.node));
+
+    let step = match (player, npc, node) {
+        (Some((player_transform, actions)), Some((npc_transform, _)), Some(node)) => {
+            let walked_away = player_transform
+                .translation()
+                .distance(npc_transform.translation())
+                > NPC_REACH * 2.0;
+            let clicked = choice_events.iter().last().map(|event| event.index);
+            let choice_count = node.choices.len().max(1);
+
+            if walked_away {
+                DialogueStep::End
+            } else if let Some(index) = clicked.or(actions.interact.then_some(active.selected)) {
+                match node.choices.get(index) {
+                    Some(choice) => {
+                        for action in choice.actions.iter() {
+                            dialogue_events.send(DialogueEvent {
+                                player: active.player,
+                                npc: active.npc,
+                                action: action.clone(),
+                            });
+                        }
+                        match &choice.next {
+                            Some(next) => DialogueStep::Goto(next.clone()),
+                            None => DialogueStep::End,
+                        }
+                    }
+                    None => DialogueStep::End,
+                }
+            } else if actions.menu_up {
+                DialogueStep::Select((active.selected + choice_count - 1) % choice_count)
+            } else if actions.menu_down {
+                DialogueStep::Select((active.selected + 1) % choice_count)
+            } else {
+                DialogueStep::Stay
+            }
+        }
+        _ => DialogueStep::End,
+    };
+
+    match step {
+        DialogueStep::Stay => {}
+        DialogueStep::Select(selected) => {
+            if let Some(active) = dialogue_state.active.as_mut() {
+                active.selected = selected;
+            }
+        }
+        DialogueStep::Goto(node) => {
+            if let Some(active) = dialogue_state.active.as_mut() {
+                active.node = node;
+                active.selected = 0;
+            }
+        }
+        DialogueStep::End => {
+            let npc_entity = active.npc;
+            if let Some((_, npc)) = npc {
+                commands.entity(npc_entity).insert(npc_prompt(npc));
+            }
+            dialogue_state.active = None;
+        }
+    }
+}
+
+pub fn npc_talk_animation(
+    dialogue_state: Res<DialogueState>,
+    mut npc_query: Query<(Entity, &mut Animations, &mut TextureAtlasSprite), With<Npc>>,
+) {
+    if !dialogue_state.is_changed() {
+        return;
+    }
+
+    let talking = dialogue_state.active.as_ref().map(|active| active.npc);
+    for (npc_entity, mut animations, mut sprite) in npc_query.iter_mut() {
+        let animation = if talking == Some(npc_entity) { 1 } else { 0 };
+        if animations.current_animation != animation {
+            animations.current_animation = animation;
+            animations.animations[animation].current_frame = 0;
+            sprite.index = animations.animations[animation].frames[0];
+        }
+    }
+}
+
+pub fn give_dialogue_items(
+    mut dialogue_events: EventReader<DialogueEvent>,
+    mut player_query: Query<(&mut Health, &MaxHealth, &mut Weapon, &mut Inventory), With<Player>>,
+    mut tracker: ResMut<MissionTracker>,
+) {
+    for event in dialogue_events.iter() {
+        let pickup = match &event.action {
+            DialogueAction::Give(kind, amount) => Pickup {
+                kind: *kind,
+                amount: *amount,
+                fire_rate: 0.1,
+                ..default()
+            },
+            DialogueAction::GiveKey(key) => Pickup {
+                kind: PickupKind::Key,
+                key: Some(key.clone()),
+                ..default()
+            },
+            DialogueAction::StartObjective(kind, goal) => {
+                start_objective(&mut tracker, Objective::new(*kind, *goal));
+                continue;
+            }
+            DialogueAction::Follow => continue,
+        };
+        let Ok((mut health, max_health, mut weapon, mut inventory)) = player_query.get_mut(event.player) else {
+            continue;
+        };
+        if apply_pickup(&pickup, &mut health, max_health, &mut weapon, &mut inventory) {
+            println!("Got {:?} from a survivor", pickup.kind);
+        }
+    }
+}
+
+/// Tracked with the objectives of the current level, unless one like it is still open
+fn start_objective(tracker: &mut MissionTracker, objective: Objective) {
+    let Some(level) = tracker.current.clone() else {
+        return;
+    };
+    let objectives = tracker.levels.entry(level).or_default();
+    if objectives.iter().any(|open| open.kind == objective.kind && !open.completed()) {
+        return;
+    }
+    println!("Objective: {}", objective.description());
+    objectives.push(objective);
+}
+
+pub fn end_dialogue(
+    mut dialogue_state: ResMut<DialogueState>,
+) {
+    dialogue_state.active = None;
+}
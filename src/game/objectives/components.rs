@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::ldtk::FieldInstance;
+use bevy_ecs_ldtk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::fields::*;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    KillZombies,
+    ReachExit,
+    Survive,
+    RescueSurvivors,
+    CollectItems,
+}
+
+/// Goal of a level, done once `progress` reaches `goal`
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Objective {
+    pub kind: ObjectiveKind,
+    /// Count for kills, rescues and items, seconds for `Survive`
+    pub goal: f32,
+    pub progress: f32,
+}
+
+impl Objective {
+    pub fn new(kind: ObjectiveKind, goal: f32) -> Self {
+        Self {
+            kind,
+            goal,
+            progress: 0.0,
+        }
+    }
+
+    pub fn completed(&self) -> bool {
+        self.progress >= self.goal
+    }
+
+    /// Line shown in the HUD objective list
+    pub fn description(&self) -> String {
+        let progress = self.progress.min(self.goal);
+        match self.kind {
+            ObjectiveKind::KillZombies => format!("Kill zombies {}/{}", progress as u32, self.goal as u32),
+            ObjectiveKind::ReachExit => "Reach the exit".to_string(),
+            ObjectiveKind::Survive => format!("Survive {:.0}s", (self.goal - progress).ceil()),
+            ObjectiveKind::RescueSurvivors => format!("Rescue survivors {}/{}", progress as u32, self.goal as u32),
+            ObjectiveKind::CollectItems => format!("Collect items {}/{}", progress as u32, self.goal as u32),
+        }
+    }
+}
+
+/// Reads the `KillZombies`, `ReachExit`, `Survive`, `RescueSurvivors` and `CollectItems` level fields,
+/// levels without any of them have no objectives
+pub fn level_objectives(fields: &[FieldInstance]) -> Vec<Objective> {
+    let counted = [
+        (ObjectiveKind::KillZombies, int_field(fields, "KillZombies")),
+        (ObjectiveKind::RescueSurvivors, int_field(fields, "RescueSurvivors")),
+        (ObjectiveKind::CollectItems, int_field(fields, "CollectItems")),
+    ];
+
+    let mut objectives: Vec<Objective> = counted
+        .into_iter()
+        .filter_map(|(kind, count)| count.filter(|count| *count > 0).map(|count| Objective::new(kind, count as f32)))
+        .collect();
+    if let Some(seconds) = float_field(fields, "Survive").filter(|seconds| *seconds > 0.0) {
+        objectives.push(Objective::new(ObjectiveKind::Survive, seconds));
+    }
+    if bool_field(fields, "ReachExit").unwrap_or(false) {
+        objectives.push(Objective::new(ObjectiveKind::ReachExit, 1.0));
+    }
+    objectives
+}
+
+/// Objectives of every level visited this run, kept so progress survives walking back and forth
+#[derive(Resource, Clone, Debug, Default)]
+pub struct MissionTracker {
+    /// Iid of the level the players are in
+    pub current: Option<String>,
+    pub levels: HashMap<String, Vec<Objective>>,
+}
+
+impl MissionTracker {
+    /// Objectives of the current level
+    pub fn objectives(&self) -> &[Objective] {
+        self.current
+            .as_ref()
+            .and_then(|level| self.levels.get(level))
+            .map(|objectives| objectives.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Sent once when an objective of `level` is done
+#[derive(Clone, PartialEq, Debug)]
+pub struct ObjectiveCompleted {
+    /// Iid of the level
+    pub level: String,
+    pub kind: ObjectiveKind,
+}
+
+/// Area placed in LDtk, a player standing in it completes `ReachExit` and leaves through it.
+/// Read from the `Level` and `World` fields, see `campaign` for where it leads.
+#[derive(Clone, PartialEq, Debug, Default, Component)]
+pub struct LevelExit {
+    pub size: Vec2,
+    /// Identifier of the level to go to
+    pub level: Option<String>,
+    /// Path of the `.ldtk` file to go to
+    pub world: Option<String>,
+}
+
+impl LevelExit {
+    pub fn contains(&self, exit_transform: &GlobalTransform, point: Vec2) -> bool {
+        let offset = (point - exit_transform.translation().truncate()).abs();
+        offset.x <= self.size.x / 2.0 && offset.y <= self.size.y / 2.0
+    }
+}
+
+impl From<&EntityInstance> for LevelExit {
+    fn from(entity_instance: &EntityInstance) -> LevelExit {
+        let fields = &entity_instance.field_instances;
+        LevelExit {
+            size: Vec2::new(entity_instance.width as f32, entity_instance.height as f32),
+            level: string_field(fields, "Level").filter(|level| !level.is_empty()),
+            world: string_field(fields, "World").filter(|world| !world.is_empty()),
+        }
+    }
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct LevelExitBundle {
+    #[bundle]
+    pub sprite_bundle: SpriteBundle,
+    #[from_entity_instance]
+    pub exit: LevelExit,
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+mod systems;
+pub mod components;
+
+use components::{LevelExitBundle, MissionTracker, ObjectiveCompleted};
+use systems::*;
+
+use crate::{AppState, SimulationState};
+
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MissionTracker>()
+            .add_event::<ObjectiveCompleted>()
+            .register_ldtk_entity::<LevelExitBundle>("Exit")
+            .add_system(setup_level_exits)
+            .add_systems(
+                (
+                    load_level_objectives,
+                    track_objectives.after(load_level_objectives),
+                    announce_objectives.after(track_objectives),
+                )
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            )
+            .add_system(reset_mission_tracker.in_schedule(OnExit(AppState::MainMenu)))
+            .add_system(reset_mission_tracker.in_schedule(OnExit(AppState::GameOver)));
+    }
+}
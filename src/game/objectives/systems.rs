@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy_ecs_ldtk::prelude::*;
+
+use super::components::*;
+use crate::events::{LevelEntered, LevelExited};
+use crate::game::enemies::components::ZombieKilled;
+use crate::game::pickups::components::PickupCollected;
+use crate::game::player::components::Player;
+use crate::game::rescue::components::SurvivorRescued;
+
+pub fn setup_level_exits(
+    mut commands: Commands,
+    exit_query: Query<(Entity, &LevelExit), Added<LevelExit>>,
+) {
+    for (exit_entity, exit) in exit_query.iter() {
+        commands
+            .entity(exit_entity)
+            .insert(Sprite {
+                color: Color::rgba(0.91, 0.77, 0.28, 0.25),
+                custom_size: Some(exit.size),
+                ..Default::default()
+            })
+            .insert(DEFAULT_IMAGE_HANDLE.typed::<Image>());
+    }
+}
+
+/// Switches the tracker to the entered level, reading its objectives the first time
+pub fn load_level_objectives(
+    mut entered_events: EventReader<LevelEntered>,
+    mut exited_events: EventReader<LevelExited>,
+    level_query: Query<&Handle<LdtkLevel>>,
+    ldtk_levels: Res<Assets<LdtkLevel>>,
+    mut tracker: ResMut<MissionTracker>,
+) {
+    for event in exited_events.iter() {
+        if tracker.current.as_ref() == Some(&event.iid) {
+            tracker.current = None;
+        }
+    }
+
+    for event in entered_events.iter() {
+        let Some(level) = level_query
+            .iter()
+            .filter_map(|level_handle| ldtk_levels.get(level_handle))
+            .map(|ldtk_level| &ldtk_level.level)
+            .find(|level| level.iid == event.iid) else {
+            continue;
+        };
+
+        tracker
+            .levels
+            .entry(level.iid.clone())
+            .or_insert_with(|| level_objectives(&level.field_instances));
+        tracker.current = Some(level.iid.clone());
+        for objective in tracker.objectives() {
+            println!("Objective: {}", objective.description());
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn track_objectives(
+    time: Res<Time>,
+    mut tracker: ResMut<MissionTracker>,
+    mut killed_events: EventReader<ZombieKilled>,
+    mut rescued_events: EventReader<SurvivorRescued>,
+    mut collected_events: EventReader<PickupCollected>,
+    mut completed_events: EventWriter<ObjectiveCompleted>,
+    exit_query: Query<(&GlobalTransform, &LevelExit)>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+) {
+    let kills = killed_events.iter().count() as f32;
+    let rescues = rescued_events.iter().count() as f32;
+    let items = collected_events.iter().count() as f32;
+    let at_exit = player_query.iter().any(|player| {
+        exit_query
+            .iter()
+            .any(|(exit_transform, exit)| exit.contains(exit_transform, player.translation().truncate()))
+    });
+
+    let Some(level) = tracker.current.clone() else {
+        return;
+    };
+    let Some(objectives) = tracker.levels.get_mut(&level) else {
+        return;
+    };
+    for objective in objectives.iter_mut().filter(|objective| !objective.completed()) {
+        objective.progress += match objective.kind {
+            ObjectiveKind::KillZombies => kills,
+            ObjectiveKind::ReachExit => if at_exit { 1.0 } else { 0.0 },
+            ObjectiveKind::Survive => time.delta_seconds(),
+            ObjectiveKind::RescueSurvivors => rescues,
+            ObjectiveKind::CollectItems => items,
+        };
+        if objective.completed() {
+            completed_events.send(ObjectiveCompleted {
+                level: level.clone(),
+                kind: objective.kind,
+            });
+        }
+    }
+}
+
+pub fn announce_objectives(
+    mut completed_events: EventReader<ObjectiveCompleted>,
+    tracker: Res<MissionTracker>,
+) {
+    for event in completed_events.iter() {
+        println!("Objective completed: {:?}", event.kind);
+        let level_done = tracker
+            .levels
+            .get(&event.level)
+            .map_or(false, |objectives| objectives.iter().all(|objective| objective.completed()));
+        if level_done {
+            println!("All objectives of the level are done");
+        }
+    }
+}
+
+pub fn reset_mission_tracker(
+    mut tracker: ResMut<MissionTracker>,
+) {
+    *tracker = MissionTracker::default();
+}
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+use crate::components::ColliderBundle;
+use crate::fields::*;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Deserialize)]
+pub enum PickupKind {
+    #[default]
+    HealthPack,
+    AmmoBox,
+    Grenade,
+    Weapon,
+    Key,
+}
+
+/// Item lying in the level, read from the LDtk `Amount`, `Respawn`, `Interact`, `FireRate` and `Key` fields
+#[derive(Clone, Debug, Default, Component)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    pub amount: i32,
+    /// Seconds between shots, only used by weapon pickups
+    pub fire_rate: f32,
+    /// Only picked up when the player presses interact next to it
+    pub needs_interact: bool,
+    pub respawn: Option<Timer>,
+    /// Which doors a key pickup unlocks
+    pub key: Option<String>,
+}
+
+impl From<&EntityInstance> for Pickup {
+    fn from(entity_instance: &EntityInstance) -> Pickup {
+        let fields = &entity_instance.field_instances;
+        let kind = match entity_instance.identifier.as_ref() {
+            "AmmoBox" => PickupKind::AmmoBox,
+            "GrenadePickup" => PickupKind::Grenade,
+            "WeaponPickup" => PickupKind::Weapon,
+            "Key" => PickupKind::Key,
+            _ => PickupKind::HealthPack,
+        };
+        let respawn = float_field(fields, "Respawn")
+            .filter(|seconds| *seconds > 0.0)
+            .map(|seconds| Timer::from_seconds(seconds, TimerMode::Once));
+
+        Pickup {
+            kind,
+            amount: int_field(fields, "Amount").unwrap_or(1),
+            fire_rate: float_field(fields, "FireRate")
+                .filter(|seconds| *seconds > 0.0)
+                .unwrap_or(0.1),
+            needs_interact: bool_field(fields, "Interact").unwrap_or(false),
+            respawn,
+            key: string_field(fields, "Key"),
+        }
+    }
+}
+
+/// Sent when a player takes a pickup
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct PickupCollected;
+
+/// Picked up, hidden until its respawn timer runs out
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
+pub struct Collected;
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct PickupBundle {
+    #[bundle]
+    pub sprite_bundle: SpriteBundle,
+    #[from_entity_instance]
+    #[bundle]
+    pub collider_bundle: ColliderBundle,
+    pub sensor: Sensor,
+    #[from_entity_instance]
+    pub pickup: Pickup,
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+pub(crate) mod systems;
+pub mod components;
+
+use components::{PickupBundle, PickupCollected};
+use systems::*;
+
+use crate::{AppState, HostGameplaySet, SimulationState};
+use crate::game::interaction::systems::send_interact_events;
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PickupCollected>()
+            .register_ldtk_entity::<PickupBundle>("HealthPack")
+            .register_ldtk_entity::<PickupBundle>("AmmoBox")
+            .register_ldtk_entity::<PickupBundle>("GrenadePickup")
+            .register_ldtk_entity::<PickupBundle>("WeaponPickup")
+            .register_ldtk_entity::<PickupBundle>("Key")
+            .add_system(pickup_appearance)
+            .add_systems(
+                (
+                    collect_pickups,
+                    interact_pickups.after(send_interact_events),
+                    respawn_pickups,
+                )
+                .in_set(HostGameplaySet)
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            );
+    }
+}
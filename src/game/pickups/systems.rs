@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+use crate::components::{FireRate, Health, Inventory, MaxHealth, Weapon};
+use crate::game::interaction::components::{InteractEvent, Interactable};
+use crate::game::player::components::Player;
+
+/// How close a player has to stand to pick up an item that needs interact
+const PICKUP_REACH: f32 = 16.0;
+
+fn pickup_prompt(kind: PickupKind) -> &'static str {
+    match kind {
+        PickupKind::HealthPack => "Pick up health pack",
+        PickupKind::AmmoBox => "Pick up ammo",
+        PickupKind::Grenade => "Pick up grenades",
+        PickupKind::Weapon => "Pick up weapon",
+        PickupKind::Key => "Pick up key",
+    }
+}
+
+fn pickup_interactable(pickup: &Pickup) -> Interactable {
+    Interactable::new(PICKUP_REACH, pickup_prompt(pickup.kind))
+}
+
+pub fn pickup_appearance(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pickup_query: Query<(Entity, &Pickup, &mut Sprite, &mut Handle<Image>), Added<Pickup>>,
+) {
+    for (pickup_entity, pickup, mut sprite, mut texture) in pickup_query.iter_mut() {
+        if pickup.needs_interact {
+            commands.entity(pickup_entity).insert(pickup_interactable(pickup));
+        }
+        match pickup.kind {
+            PickupKind::HealthPack => {
+                sprite.color = Color::rgb(0.84, 0.14, 0.07);
+                sprite.custom_size = Some(Vec2::splat(8.0));
+            }
+            PickupKind::AmmoBox => {
+                sprite.color = Color::rgb(0.89, 0.65, 0.45);
+                sprite.custom_size = Some(Vec2::new(10.0, 6.0));
+            }
+            PickupKind::Grenade => {
+                *texture = asset_server.load("granade.png");
+                sprite.custom_size = Some(Vec2::new(6.0, 12.0));
+            }
+            PickupKind::Weapon => {
+                sprite.color = Color::rgb(0.35, 0.41, 0.53);
+                sprite.custom_size = Some(Vec2::new(14.0, 5.0));
+            }
+            PickupKind::Key => {
+                sprite.color = Color::rgb(1.0, 0.91, 0.38);
+                sprite.custom_size = Some(Vec2::new(8.0, 4.0));
+            }
+        }
+    }
+}
+
+/// Applies the pickup, returns false if the player had no use for it
+pub fn apply_pickup(
+    pickup: &Pickup,
+    health: &mut Health,
+    max_health: &MaxHealth,
+    weapon: &mut Weapon,
+    inventory: &mut Inventory,
+) -> bool {
+    match pickup.kind {
+        PickupKind::HealthPack => {
+            if health.health_points >= max_health.health_points {
+                return false;
+            }
+            health.health_points = (health.health_points + pickup.amount).min(max_health.health_points);
+        }
+        PickupKind::AmmoBox => {
+            if weapon.ammo.bullets >= weapon.mag_size {
+                return false;
+            }
+            weapon.ammo.bullets = (weapon.ammo.bullets + pickup.amount.max(0) as u32).min(weapon.mag_size);
+            weapon.reloading = false;
+            weapon.reload_timer.reload_timer.reset();
+        }
+        PickupKind::Grenade => {
+            inventory.grenades += pickup.amount.max(0) as u32;
+        }
+        PickupKind::Weapon => {
+            weapon.mag_size = pickup.amount.max(1) as u32;
+            weapon.ammo.bullets = weapon.mag_size;
+            weapon.fire_rate = FireRate {
+                timer: Timer::from_seconds(pickup.fire_rate, TimerMode::Repeating),
+            };
+            weapon.reloading = false;
+            weapon.reload_timer.reload_timer.reset();
+        }
+        PickupKind::Key => {
+            let Some(key) = pickup.key.clone() else {
+                return false;
+            };
+            inventory.keys.push(key);
+        }
+    }
+    true
+}
+
+/// Hides the pickup until it respawns, or removes it for good
+fn take_pickup(
+    commands: &mut Commands,
+    collected_events: &mut EventWriter<PickupCollected>,
+    pickup_entity: Entity,
+    pickup: &mut Pickup,
+    visibility: &mut Visibility,
+) {
+    println!("Picked up {:?}", pickup.kind);
+    collected_events.send(PickupCollected);
+    match pickup.respawn.as_mut() {
+        Some(timer) => {
+            timer.reset();
+            *visibility = Visibility::Hidden;
+            commands
+                .entity(pickup_entity)
+                .insert(Collected)
+                .remove::<Interactable>();
+        }
+        None => commands.entity(pickup_entity).despawn_recursive(),
+    }
+}
+
+pub fn collect_pickups(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    mut pickup_query: Query<(Entity, &mut Pickup, &mut Visibility), Without<Collected>>,
+    mut player_query: Query<(Entity, &mut Health, &MaxHealth, &mut Weapon, &mut Inventory), With<Player>>,
+    mut collected_events: EventWriter<PickupCollected>,
+) {
+    for (pickup_entity, mut pickup, mut visibility) in pickup_query.iter_mut() {
+        if pickup.needs_interact {
+            continue;
+        }
+        for (player, mut health, max_health, mut weapon, mut inventory) in player_query.iter_mut() {
+            if rapier_context.intersection_pair(pickup_entity, player) != Some(true) {
+                continue;
+            }
+            if !apply_pickup(&pickup, &mut health, max_health, &mut weapon, &mut inventory) {
+                continue;
+            }
+
+            take_pickup(&mut commands, &mut collected_events, pickup_entity, &mut pickup, &mut visibility);
+            break;
+        }
+    }
+}
+
+pub fn interact_pickups(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    mut pickup_query: Query<(&mut Pickup, &mut Visibility), Without<Collected>>,
+    mut player_query: Query<(&mut Health, &MaxHealth, &mut Weapon, &mut Inventory), With<Player>>,
+    mut collected_events: EventWriter<PickupCollected>,
+) {
+    for event in interact_events.iter() {
+        let Ok((mut pickup, mut visibility)) = pickup_query.get_mut(event.target) else {
+            continue;
+        };
+        let Ok((mut health, max_health, mut weapon, mut inventory)) = player_query.get_mut(event.player) else {
+            continue;
+        };
+        if !apply_pickup(&pickup, &mut health, max_health, &mut weapon, &mut inventory) {
+            continue;
+        }
+
+        take_pickup(&mut commands, &mut collected_events, event.target, &mut pickup, &mut visibility);
+    }
+}
+
+pub fn respawn_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pickup_query: Query<(Entity, &mut Pickup, &mut Visibility), With<Collected>>,
+) {
+    for (pickup_entity, mut pickup, mut visibility) in pickup_query.iter_mut() {
+        let Some(timer) = pickup.respawn.as_mut() else {
+            continue;
+        };
+        timer.tick(time.delta());
+        if timer.finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(pickup_entity).remove::<Collected>();
+            if pickup.needs_interact {
+                commands.entity(pickup_entity).insert(pickup_interactable(&pickup));
+            }
+        }
+    }
+}
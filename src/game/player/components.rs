@@ -1,7 +1,121 @@
 use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::systems::PLAYER_HEALTH;
+use crate::fields::*;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
 pub struct Player;
 
+/// What a player starts with
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Loadout {
+    pub health: i32,
+    pub bullets: u32,
+    pub mag_size: u32,
+    pub grenades: u32,
+}
+
+impl Default for Loadout {
+    fn default() -> Self {
+        Self {
+            health: PLAYER_HEALTH,
+            bullets: 0,
+            mag_size: 30,
+            grenades: 3,
+        }
+    }
+}
+
+/// Where players start, placed in LDtk.
+/// Read from the `Slot`, `Facing`, `Health`, `Ammo`, `MagSize` and `Grenades` fields.
+#[derive(Clone, PartialEq, Debug, Default, Component)]
+pub struct PlayerSpawn {
+    /// Player slot that starts here, `None` for every slot without a spawn of its own
+    pub slot: Option<usize>,
+    pub facing_left: bool,
+    pub loadout: Loadout,
+}
+
+impl From<&EntityInstance> for PlayerSpawn {
+    fn from(entity_instance: &EntityInstance) -> PlayerSpawn {
+        let fields = &entity_instance.field_instances;
+        let default = Loadout::default();
+        PlayerSpawn {
+            slot: int_field(fields, "Slot").map(|slot| slot.max(0) as usize),
+            facing_left: string_field(fields, "Facing").map_or(false, |facing| facing.eq_ignore_ascii_case("left")),
+            loadout: Loadout {
+                health: int_field(fields, "Health").filter(|health| *health > 0).unwrap_or(default.health),
+                bullets: int_field(fields, "Ammo").map_or(default.bullets, |ammo| ammo.max(0) as u32),
+                mag_size: int_field(fields, "MagSize").map_or(default.mag_size, |mag_size| mag_size.max(1) as u32),
+                grenades: int_field(fields, "Grenades").map_or(default.grenades, |grenades| grenades.max(0) as u32),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct PlayerSpawnBundle {
+    #[from_entity_instance]
+    pub player_spawn: PlayerSpawn,
+}
+
+/// Player slot, 0 is always the keyboard and mouse player of this or the hosting machine.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Component)]
+pub struct PlayerId(pub usize);
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
-pub struct Spawn;
\ No newline at end of file
+pub enum InputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad(Gamepad),
+    /// Played by the computer, see `BotPlugin`
+    Bot,
+    /// Played on another machine, see `NetPlugin`. Holds the player slot.
+    Remote(usize),
+}
+
+/// What a player wants to do this frame, filled from its `InputDevice`.
+/// Gameplay systems read this instead of the raw keyboard/mouse/gamepad input.
+#[derive(Clone, PartialEq, Debug, Default, Component, Serialize, Deserialize)]
+pub struct PlayerActions {
+    pub movement: Vec2,
+    /// World position the player is aiming at
+    pub aim: Vec2,
+    pub fire: bool,
+    pub fire_just_pressed: bool,
+    pub reload: bool,
+    pub throw_grenade: bool,
+    pub talk: bool,
+    pub interact: bool,
+    /// Moving the selection in menus and dialogue choices
+    pub menu_up: bool,
+    pub menu_down: bool,
+}
+
+/// Sent for every bullet a player fires
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ShotFired {
+    pub position: Vec2,
+}
+
+/// Sent when a player starts reloading
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PlayerReloaded {
+    pub position: Vec2,
+}
+
+/// Sent every time a zombie gets a hit in on a player
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PlayerHurt {
+    pub position: Vec2,
+    /// Health points lost
+    pub damage: i32,
+}
+
+/// Sent for every grenade a player throws
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GrenadeThrown {
+    pub position: Vec2,
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::MouseLoc;
+use crate::game::player::components::*;
+use crate::settings::components::Settings;
+
+const GAMEPAD_DEADZONE: f32 = 0.2;
+const GAMEPAD_AIM_DISTANCE: f32 = 80.0;
+
+/// Runs every frame. Presses read by the fixed gameplay steps add up until `clear_fixed_actions`,
+/// so none is lost or seen twice however many steps a frame has.
+pub fn read_player_input(
+    keyboard: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    mouse_input: Res<Input<MouseButton>>,
+    mouse_pos: Res<MouseLoc>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut player_query: Query<(&mut PlayerActions, &InputDevice, &Transform), With<Player>>,
+) {
+    for (mut actions, device, transform) in &mut player_query {
+        match *device {
+            InputDevice::KeyboardMouse => {
+                let keys = &settings.key_bindings;
+                let right = if keyboard.pressed(keys.move_right) { 1. } else { 0. };
+                let left = if keyboard.pressed(keys.move_left) { 1. } else { 0. };
+                let up = if keyboard.pressed(keys.move_up) { 1. } else { 0. };
+                let down = if keyboard.pressed(keys.move_down) { 1. } else { 0. };
+
+                actions.movement = Vec2::new(right - left, up - down);
+                actions.aim = mouse_pos.loc;
+                actions.fire = mouse_input.pressed(MouseButton::Left);
+                actions.fire_just_pressed |= mouse_input.just_pressed(MouseButton::Left);
+                actions.reload |= keyboard.just_pressed(keys.reload);
+                actions.throw_grenade |= keyboard.just_pressed(keys.throw_grenade);
+                actions.talk = keyboard.just_pressed(keys.talk);
+                actions.interact = keyboard.just_pressed(keys.interact);
+                actions.menu_up = keyboard.just_pressed(KeyCode::Up);
+                actions.menu_down = keyboard.just_pressed(KeyCode::Down);
+            }
+            InputDevice::Gamepad(gamepad) => {
+                let axis = |axis_type| {
+                    gamepad_axes
+                        .get(GamepadAxis::new(gamepad, axis_type))
+                        .unwrap_or(0.0)
+                };
+                let button = |button_type| GamepadButton::new(gamepad, button_type);
+
+                let movement = Vec2::new(axis(GamepadAxisType::LeftStickX), axis(GamepadAxisType::LeftStickY));
+                actions.movement = if movement.length() > GAMEPAD_DEADZONE {
+                    movement.clamp_length_max(1.0)
+                } else {
+                    Vec2::ZERO
+                };
+
+                // The right stick only gives a direction, so aim at a point in front of the player.
+                // With the stick released keep aiming the same way, or the way we walk.
+                let player_position = transform.translation.truncate();
+                let look = Vec2::new(axis(GamepadAxisType::RightStickX), axis(GamepadAxisType::RightStickY));
+                let aim_direction = if look.length() > GAMEPAD_DEADZONE {
+                    look.normalize()
+                } else if actions.movement != Vec2::ZERO {
+                    actions.movement.normalize()
+                } else {
+                    (actions.aim - player_position).try_normalize().unwrap_or(Vec2::X)
+                };
+                actions.aim = player_position + aim_direction * GAMEPAD_AIM_DISTANCE;
+
+                actions.fire = gamepad_buttons.pressed(button(GamepadButtonType::RightTrigger2));
+                actions.fire_just_pressed |= gamepad_buttons.just_pressed(button(GamepadButtonType::RightTrigger2));
+                actions.reload |= gamepad_buttons.just_pressed(button(GamepadButtonType::West));
+                actions.throw_grenade |= gamepad_buttons.just_pressed(button(GamepadButtonType::RightTrigger));
+                actions.talk = gamepad_buttons.just_pressed(button(GamepadButtonType::North));
+                actions.interact = gamepad_buttons.just_pressed(button(GamepadButtonType::South));
+                actions.menu_up = gamepad_buttons.just_pressed(button(GamepadButtonType::DPadUp));
+                actions.menu_down = gamepad_buttons.just_pressed(button(GamepadButtonType::DPadDown));
+            }
+            // Filled by `drive_bots` and by what the clients send
+            InputDevice::Bot | InputDevice::Remote(_) => {}
+        }
+    }
+}
@@ -1,11 +1,18 @@
 use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
 
-use self::systems::*;
-use crate::AppState;
+use crate::{AppState, FixedGameplaySet, SimulationState};
+use crate::game::npcs::systems::not_in_dialogue;
+use crate::replay::systems::not_playing_back;
 
+use components::{GrenadeThrown, PlayerHurt, PlayerReloaded, PlayerSpawnBundle, ShotFired};
+use input::*;
+use resources::{Lives, PlayerSpawns, RestoredSpawns, Respawns};
 use systems::*;
 
 pub mod components;
+pub mod resources;
+pub(crate) mod input;
 pub(crate) mod systems;
 
 pub struct PlayerPlugin;
@@ -13,18 +20,51 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App){
         app
+        .add_event::<ShotFired>()
+        .add_event::<PlayerReloaded>()
+        .add_event::<PlayerHurt>()
+        .add_event::<GrenadeThrown>()
+        .init_resource::<PlayerSpawns>()
+        .init_resource::<RestoredSpawns>()
+        .init_resource::<Lives>()
+        .init_resource::<Respawns>()
+        .register_ldtk_entity::<PlayerSpawnBundle>("PlayerSpawn")
         .add_system(spawn_player.in_schedule(OnEnter(AppState::Game)))
+        .add_system(reset_lives.in_schedule(OnExit(AppState::MainMenu)))
+        .add_system(reset_lives.in_schedule(OnExit(AppState::GameOver)))
+        .add_system(despawn_players.in_schedule(OnEnter(AppState::GameOver)))
+        .add_system(despawn_players.in_schedule(OnEnter(AppState::MainMenu)))
+        .add_system(
+            read_player_input
+                .run_if(not_playing_back)
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+        )
         .add_systems(
             (
-                player_movement, 
-                player_reload, 
-                player_shoot, 
-                player_throw_grenade,
                 rotate_player,
                 talk,
+                join_players,
+            ).after(read_player_input)
+            .in_set(OnUpdate(AppState::Game))
+            .in_set(OnUpdate(SimulationState::Running))
+        )
+        .add_systems(
+            (
+                player_movement,
+                player_reload,
+                player_shoot.run_if(not_in_dialogue),
+                player_throw_grenade.run_if(not_in_dialogue),
                 player_take_dmg,
                 kill_player,
-            ).in_set(OnUpdate(AppState::Game))
+                respawn_players.after(kill_player),
+                clear_fixed_actions
+                    .after(player_reload)
+                    .after(player_shoot)
+                    .after(player_throw_grenade),
+            )
+            .in_set(FixedGameplaySet)
+            .in_schedule(CoreSchedule::FixedUpdate)
         );
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::components::{InputDevice, Loadout, PlayerId};
+
+/// Where a player slot (re)spawns, facing which way and with what
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpawnPoint {
+    pub position: Vec3,
+    pub facing_left: bool,
+    pub loadout: Loadout,
+}
+
+/// Spawn point of every player slot in the current map, filled when the players spawn
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PlayerSpawns {
+    pub points: HashMap<PlayerId, SpawnPoint>,
+}
+
+/// Spawn points taking the place of the map's PlayerSpawns the next time players spawn,
+/// set when a saved game is loaded
+#[derive(Resource, Clone, Debug, Default)]
+pub struct RestoredSpawns {
+    pub points: HashMap<PlayerId, SpawnPoint>,
+}
+
+/// Respawns left for the whole team this run
+#[derive(Resource, Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Lives {
+    pub remaining: u32,
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self {
+            remaining: 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PendingRespawn {
+    pub player_id: PlayerId,
+    pub device: InputDevice,
+    pub timer: Timer,
+}
+
+/// Players that died and come back once their timer runs out
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Respawns {
+    pub pending: Vec<PendingRespawn>,
+}
@@ -1,41 +1,52 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
-use std::thread;
-use std::time::Duration;
 
-use crate::MouseLoc;
 use crate::game::player::components::*;
-use crate::components::{ColliderBundle, Health, PlayerBundle, Ammo};
+use crate::game::player::resources::{Lives, PendingRespawn, PlayerSpawns, RestoredSpawns, Respawns, SpawnPoint};
+use crate::components::{ColliderBundle, Health, Inventory, MaxHealth, PlayerBundle, Ammo};
 
-use crate::components::{Weapon, Bullet, BulletBundle, Grenade, GrenadeBundle, DetonationTimer};
+use crate::components::{Weapon, Bullet, BulletBundle, Grenade, GrenadeBundle, DetonationTimer, Shooter, StateScoped};
 use crate::game::enemies::components::Zombie;
 use crate::graphics::*;
 use crate::AppState;
 
+pub const PLAYER_HEALTH: i32 = 10;
+/// Pixels a second a player walks, half of it while reloading
+pub const PLAYER_SPEED: f32 = 150.0;
+/// Seconds before a dead player comes back at the checkpoint
+const RESPAWN_DELAY: f32 = 2.0;
+
 //TODO add another system that drives player animations
 
 pub fn rotate_player(
-    mouse_pos: ResMut<MouseLoc>,
-    mut player_pos: Query<&mut Transform, With<Player>>,
-    mut player_sprite: Query<&mut TextureAtlasSprite, With<Player>>,
+    mut player_query: Query<(&Transform, &PlayerActions, &mut TextureAtlasSprite), With<Player>>,
 ) {
-    for transform in &mut player_pos {
-        if let Ok(mut sprite) = player_sprite.get_single_mut() {
-            //println!("World position: {}/{}", world_position.x, world_position.y);
-            if mouse_pos.loc.x < transform.translation.x && !sprite.flip_x {
-                sprite.flip_x = true;
-            } else if mouse_pos.loc.x > transform.translation.x && sprite.flip_x {
-                sprite.flip_x = false;
-            }
+    for (transform, actions, mut sprite) in &mut player_query {
+        if actions.aim.x < transform.translation.x && !sprite.flip_x {
+            sprite.flip_x = true;
+        } else if actions.aim.x > transform.translation.x && sprite.flip_x {
+            sprite.flip_x = false;
         }
     }
 }
 
+/// Presses are kept until a fixed step has seen them, then let go
+pub fn clear_fixed_actions(
+    mut player_query: Query<&mut PlayerActions, With<Player>>,
+) {
+    for mut actions in player_query.iter_mut() {
+        actions.fire_just_pressed = false;
+        actions.reload = false;
+        actions.throw_grenade = false;
+    }
+}
+
 pub fn player_take_dmg(
-    mut zombies: Query<(&Transform, Entity), With<Zombie>>,
+    zombies: Query<(&Transform, Entity), With<Zombie>>,
     mut player: Query<(&mut Health, Entity, &Transform, &mut Velocity), With<Player>>,
     mut player_collisions: EventReader<CollisionEvent>,
+    mut hurt_events: EventWriter<PlayerHurt>,
     mut commands: Commands,
 ) {
     for col_event in player_collisions.iter() {
@@ -43,22 +54,26 @@ pub fn player_take_dmg(
         match col_event.to_owned() {
             CollisionEvent::Started(e1, e2, _) => {
                 for (zombie_transform, zombie_entity) in zombies.iter() {
-                    for (mut health, player_entity, mut player_transform, mut velocity) in player.iter_mut() {
-                        if player_entity.eq(&e1) || player_entity.eq(&e2) {
-                            if zombie_entity.eq(&e1) || zombie_entity.eq(&e2) {
-                                health.health_points -= 1;
-                                println!(
-                                    "Player: {:?} took 1 dmg and now has: {:?}",
-                                    commands.entity(player_entity).id(),
-                                    health.health_points
-                                );
-
-                                velocity.linvel += (zombie_transform.translation
-                                    + player_transform.translation)
-                                    .truncate()
-                                    .normalize()
-                                    * 500.0;
-                            }
+                    for (mut health, player_entity, player_transform, mut velocity) in player.iter_mut() {
+                        if (player_entity.eq(&e1) || player_entity.eq(&e2))
+                            && (zombie_entity.eq(&e1) || zombie_entity.eq(&e2))
+                        {
+                            health.health_points -= 1;
+                            hurt_events.send(PlayerHurt {
+                                position: player_transform.translation.truncate(),
+                                damage: 1,
+                            });
+                            println!(
+                                "Player: {:?} took 1 dmg and now has: {:?}",
+                                commands.entity(player_entity).id(),
+                                health.health_points
+                            );
+
+                            velocity.linvel += (zombie_transform.translation
+                                + player_transform.translation)
+                                .truncate()
+                                .normalize()
+                                * 500.0;
                         }
                     }
                 }
@@ -69,32 +84,108 @@ pub fn player_take_dmg(
 }
 
 pub fn kill_player(
-    player_health: Query<(Entity, &Health), With<Player>>,
+    player_health: Query<(Entity, &Health, &PlayerId, &InputDevice), With<Player>>,
+    mut lives: ResMut<Lives>,
+    mut respawns: ResMut<Respawns>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
     mut commands: Commands
 ) {
-    if let Ok((player, health)) = player_health.get_single() {
-        if health.health_points == 0{
-            app_state_next_state.set(AppState::GameOver);
+    let mut players_alive = 0;
+    for (player, health, player_id, device) in player_health.iter() {
+        if health.health_points <= 0 {
             commands.entity(player).despawn();
-            //std::thread::sleep(Duration::new(2,0) );
-            //app_state_next_state.set(AppState::MainMenu);
+            if lives.remaining > 0 {
+                lives.remaining -= 1;
+                println!("Player {} died, {} lives left", player_id.0 + 1, lives.remaining);
+                respawns.pending.push(PendingRespawn {
+                    player_id: *player_id,
+                    device: *device,
+                    timer: Timer::from_seconds(RESPAWN_DELAY, TimerMode::Once),
+                });
+            } else {
+                println!("Player {} died", player_id.0 + 1);
+            }
+        } else {
+            players_alive += 1;
         }
     }
+
+    if players_alive == 0 && respawns.pending.is_empty() && !player_health.is_empty() {
+        app_state_next_state.set(AppState::GameOver);
+    }
+}
+
+/// Brings dead players back at their spawn point, the last checkpoint reached, with a fresh loadout.
+/// The level is left as it is.
+pub fn respawn_players(
+    mut commands: Commands,
+    fixed_time: Res<FixedTime>,
+    mut respawns: ResMut<Respawns>,
+    player_spawns: Res<PlayerSpawns>,
+    player_query: Query<&PlayerId, With<Player>>,
+    characters: Res<CharacterSheet>,
+) {
+    for respawn in respawns.pending.iter_mut() {
+        respawn.timer.tick(fixed_time.period);
+    }
+
+    let (ready, waiting): (Vec<PendingRespawn>, Vec<PendingRespawn>) = respawns
+        .pending
+        .drain(..)
+        .partition(|respawn| respawn.timer.finished());
+    respawns.pending = waiting;
+
+    for respawn in ready {
+        // Came back another way already, e.g. by loading into the next map
+        if player_query.iter().any(|player_id| *player_id == respawn.player_id) {
+            continue;
+        }
+        // Players that joined mid game have no spawn point of their own
+        let Some(spawn_point) = player_spawns
+            .points
+            .get(&respawn.player_id)
+            .or_else(|| player_spawns.points.values().next()) else {
+            continue;
+        };
+        println!("Player {} respawned", respawn.player_id.0 + 1);
+        spawn_player_at(&mut commands, &characters, spawn_point, respawn.player_id, respawn.device);
+    }
+}
+
+/// Players left over when the game ends or is quit go away with the rest of the run
+pub fn despawn_players(
+    mut commands: Commands,
+    player_query: Query<Entity, With<Player>>,
+) {
+    for player in player_query.iter() {
+        commands.entity(player).despawn_recursive();
+    }
+}
+
+/// A new run starts with full lives
+pub fn reset_lives(
+    mut lives: ResMut<Lives>,
+    mut respawns: ResMut<Respawns>,
+) {
+    *lives = Lives::default();
+    respawns.pending.clear();
 }
 
 pub fn player_reload(
-    mut weapon_query: Query<&mut Weapon, With<Player>>,
-    input: Res<Input<KeyCode>>, 
-    time: Res<Time>
-){   
-    if let Ok(mut weapon) = weapon_query.get_single_mut(){
-        if input.just_pressed(KeyCode::R) {
+    mut weapon_query: Query<(&mut Weapon, &PlayerActions, &Transform), With<Player>>,
+    mut reload_events: EventWriter<PlayerReloaded>,
+    fixed_time: Res<FixedTime>
+){
+    for (mut weapon, actions, transform) in weapon_query.iter_mut() {
+        if actions.reload {
             println!("RELOADING");
+            if !weapon.reloading {
+                reload_events.send(PlayerReloaded { position: transform.translation.truncate() });
+            }
             weapon.reloading = true;
         }
         if weapon.reloading {
-            weapon.reload_timer.reload_timer.tick(time.delta());
+            weapon.reload_timer.reload_timer.tick(fixed_time.period);
         }
         if weapon.reload_timer.reload_timer.finished() {
             weapon.ammo.bullets = weapon.mag_size;
@@ -105,271 +196,395 @@ pub fn player_reload(
     }
 }
 
+pub fn spawn_bullet(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    shooter: Entity,
+    origin: Vec3,
+    target: Vec2,
+) -> Entity {
+    let bullet_velocity = (target - origin.truncate()).normalize();
+    let angle = bullet_velocity.y.atan2(bullet_velocity.x);
+    commands.spawn(BulletBundle {
+        sprite_bundle: SpriteBundle {
+            transform: Transform {
+                translation: Vec3::from_array([
+                    origin.x + bullet_velocity.x * 8.0,
+                    origin.y + bullet_velocity.y * 10.0,
+                    0.0,
+                ]),
+                rotation: Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle),
+                ..Default::default()
+            },
+            texture: asset_server.load("bullet.png"),
+            ..Default::default()
+        },
+        collider_bundle: ColliderBundle {
+            collider: Collider::cuboid(0.5, 1.5),
+            rigid_body: RigidBody::Dynamic,
+            velocity: Velocity::linear(bullet_velocity * 500.0),
+            ..Default::default()
+        },
+        bullet: Bullet {},
+    }).insert(Sensor).insert(ActiveEvents::COLLISION_EVENTS)
+    .insert(Shooter(shooter))
+    .insert(StateScoped(AppState::Game))
+    .id()
+}
+
 pub fn player_shoot(
     mut commands: Commands,
-    mouse_input: Res<Input<MouseButton>>,
-    mouse_pos: Res<MouseLoc>,
-    player_pos: Query<&Transform, With<Player>>,
     asset_server: Res<AssetServer>,
-    mut weapon_query: Query<&mut Weapon, With<Player>>,
-    time: Res<Time>,
-    mut player_anim: Query<&mut Animations, With<Player>>,
+    fixed_time: Res<FixedTime>,
+    mut player_query: Query<(Entity, &Transform, &mut Weapon, &PlayerActions, &mut Animations), With<Player>>,
+    mut shot_events: EventWriter<ShotFired>,
 ) {
-
-    if mouse_input.just_pressed(MouseButton::Left) {
-        if let Ok(mut weapon) = weapon_query.get_single_mut(){
+    for (player, player_position, mut weapon, actions, mut anim) in player_query.iter_mut() {
+        if actions.fire_just_pressed {
             weapon.fire_rate.timer.reset();
 
-            if weapon.ammo.bullets != 0{
-                if let Ok(mut anim) = player_anim.get_single_mut() {
-                    anim.current_animation = 2;
-                }
-    
-                if let Ok(player_position) = player_pos.get_single() {
-
-                    let bullet_velocity = (mouse_pos.loc - player_position.translation.truncate()).normalize();
-                    let angle = bullet_velocity.y.atan2(bullet_velocity.x);
-                    commands.spawn(BulletBundle {
-                        sprite_bundle: SpriteBundle {
-                            transform: Transform {
-                                translation: Vec3::from_array([
-                                    player_position.translation.x + bullet_velocity.x * 8.0,
-                                    player_position.translation.y + bullet_velocity.y * 10.0,
-                                    0.0,
-                                ]),
-                                rotation: Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle),
-                                ..Default::default()
-                            },
-                            texture: asset_server.load("bullet.png"),
-                            ..Default::default()
-                        },
-                        collider_bundle: ColliderBundle {
-                            collider: Collider::cuboid(0.5, 1.5),
-                            rigid_body: RigidBody::Dynamic,
-                            velocity: Velocity::linear(bullet_velocity * 500.0),
-                            ..Default::default()
-                        },
-                        bullet: Bullet {},
-                    }).insert(Sensor).insert(ActiveEvents::COLLISION_EVENTS);
-
-                    weapon.ammo.bullets -= 1;
-                }
+            if weapon.ammo.bullets != 0 {
+                anim.current_animation = 2;
+                spawn_bullet(&mut commands, &asset_server, player, player_position.translation, actions.aim);
+                shot_events.send(ShotFired { position: player_position.translation.truncate() });
+                weapon.ammo.bullets -= 1;
             }
-        }
-    } else if mouse_input.pressed(MouseButton::Left){ 
-        if let Ok(mut weapon) = weapon_query.get_single_mut(){
-            weapon.fire_rate.timer.tick(time.delta());
-            if weapon.ammo.bullets != 0{
-                if let Ok(mut anim) = player_anim.get_single_mut() {
-                    anim.current_animation = 2;
-                }
+        } else if actions.fire {
+            weapon.fire_rate.timer.tick(fixed_time.period);
+            if weapon.ammo.bullets != 0 {
+                anim.current_animation = 2;
 
-                if weapon.fire_rate.to_owned().timer.finished() && weapon.ammo.bullets != 0{
-                    
-                    if let Ok(player_position) = player_pos.get_single() {
-
-                        let bullet_velocity = (mouse_pos.loc - player_position.translation.truncate()).normalize();
-                        let angle = bullet_velocity.y.atan2(bullet_velocity.x);
-                        commands.spawn(BulletBundle {
-                            sprite_bundle: SpriteBundle {
-                                transform: Transform {
-                                    translation: Vec3::from_array([
-                                        player_position.translation.x + bullet_velocity.x * 8.0,
-                                        player_position.translation.y + bullet_velocity.y * 10.0,
-                                        0.0,
-                                    ]),
-                                    rotation: Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle),
-                                    ..Default::default()
-                                },
-                                texture: asset_server.load("bullet.png"),
-                                ..Default::default()
-                            },
-                            collider_bundle: ColliderBundle {
-                                collider: Collider::cuboid(0.5, 1.5),
-                                rigid_body: RigidBody::Dynamic,
-                                velocity: Velocity::linear(bullet_velocity * 500.0),
-                                ..Default::default()
-                            },
-                            bullet: Bullet {},
-                        }).insert(Sensor).insert(ActiveEvents::COLLISION_EVENTS);
-
-                        weapon.ammo.bullets -= 1;
-                    }
+                if weapon.fire_rate.timer.finished() {
+                    spawn_bullet(&mut commands, &asset_server, player, player_position.translation, actions.aim);
+                    shot_events.send(ShotFired { position: player_position.translation.truncate() });
+                    weapon.ammo.bullets -= 1;
                 }
             }
         }
     }
 }
 
+pub fn spawn_grenade(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    origin: Vec3,
+    target: Vec2,
+) -> Entity {
+    let grenade_velocity = (target - origin.truncate()).normalize();
+    let angle = grenade_velocity.y.atan2(grenade_velocity.x);
+    commands.spawn(GrenadeBundle {
+        sprite_bundle: SpriteBundle {
+            transform: Transform {
+                translation: Vec3::from_array([
+                    origin.x + grenade_velocity.x * 8.0,
+                    origin.y + grenade_velocity.y * 10.0,
+                    0.0,
+                ]),
+                rotation: Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle),
+                ..Default::default()
+            },
+            texture: asset_server.load("granade.png"),
+            ..Default::default()
+        },
+        collider_bundle: ColliderBundle {
+            collider: Collider::cuboid(0.5, 1.5),
+            rigid_body: RigidBody::Dynamic,
+            velocity: Velocity::linear(grenade_velocity * 100.0),
+            ..Default::default()
+        },
+        timer: DetonationTimer {
+            detonation_timer: Timer::from_seconds(2.0, TimerMode::Once),
+        },
+        grenade: Grenade,
+    }).insert(StateScoped(AppState::Game))
+    .id()
+}
+
 pub fn player_throw_grenade(
     mut commands: Commands,
-    input: Res<Input<KeyCode>>,
-    mouse_pos: Res<MouseLoc>,
-    player_pos: Query<&Transform, With<Player>>,
+    mut player_query: Query<(&Transform, &PlayerActions, &mut Inventory), With<Player>>,
+    mut thrown_events: EventWriter<GrenadeThrown>,
     asset_server: Res<AssetServer>,
 ) {
-    if input.just_pressed(KeyCode::G) {
-        if let Ok(player_position) = player_pos.get_single() {
-            let bullet_velocity =
-                (mouse_pos.loc - player_position.translation.truncate()).normalize();
-            let angle = bullet_velocity.y.atan2(bullet_velocity.x);
-            commands.spawn(GrenadeBundle {
-                sprite_bundle: SpriteBundle {
-                    transform: Transform {
-                        translation: Vec3::from_array([
-                            player_position.translation.x + bullet_velocity.x * 8.0,
-                            player_position.translation.y + bullet_velocity.y * 10.0,
-                            0.0,
-                        ]),
-                        rotation: Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle),
-                        ..Default::default()
-                    },
-                    texture: asset_server.load("granade.png"),
-                    ..Default::default()
-                },
-                collider_bundle: ColliderBundle {
-                    collider: Collider::cuboid(0.5, 1.5),
-                    rigid_body: RigidBody::Dynamic,
-                    velocity: Velocity::linear(bullet_velocity * 100.0),
-                    ..Default::default()
-                },
-                timer: DetonationTimer {
-                    detonation_timer: Timer::from_seconds(2.0, TimerMode::Once),
-                },
-                grenade: Grenade,
-            });
+    for (player_position, actions, mut inventory) in player_query.iter_mut() {
+        if actions.throw_grenade && inventory.grenades > 0 {
+            inventory.grenades -= 1;
+            thrown_events.send(GrenadeThrown { position: player_position.translation.truncate() });
+            spawn_grenade(&mut commands, &asset_server, player_position.translation, actions.aim);
         }
     }
 }
 
 pub fn player_movement(
-    input: Res<Input<KeyCode>>,
-    mut query: Query<&mut Velocity, With<Player>>,
-    player_weapon: Query<&Weapon, With<Player>>,
-    mut player_anim: Query<&mut Animations, With<Player>>,
+    mut query: Query<(&mut Velocity, &Weapon, &PlayerActions, &mut Animations), With<Player>>,
 ) {
-    for mut velocity in &mut query {
-        let right = if input.pressed(KeyCode::D) { 1. } else { 0. };
-        let left = if input.pressed(KeyCode::A) { 1. } else { 0. };
-        let up = if input.pressed(KeyCode::W) { 1. } else { 0. };
-        let down = if input.pressed(KeyCode::S) { 1. } else { 0. };
-
-        velocity.linvel.x = (right - left) * 150.;
-        velocity.linvel.y = (up - down) * 150.;
-
-        if let Ok(weapon) = player_weapon.get_single() {
-            if weapon.reloading {
-                velocity.linvel.x /= 2.0;
-                velocity.linvel.y /= 2.0;
-            }
+    for (mut velocity, weapon, actions, mut anim) in &mut query {
+        velocity.linvel = actions.movement * PLAYER_SPEED;
+
+        if weapon.reloading {
+            velocity.linvel.x /= 2.0;
+            velocity.linvel.y /= 2.0;
         }
 
-        if let Ok(mut anim) = player_anim.get_single_mut() {
-            if !velocity.eq(&Velocity::zero()) {
-                anim.current_animation = 0;
-            } else {
-                anim.current_animation = 3;
-            }
+        if !velocity.eq(&Velocity::zero()) {
+            anim.current_animation = 0;
+        } else {
+            anim.current_animation = 3;
         }
-        
     }
 }
 
 pub fn talk(
-    input: Res<Input<KeyCode>>,
-    characters: Res<CharacterSheet>,
-    mut player_anim: Query<&mut Animations, With<Player>>,
+    mut player_anim: Query<(&mut Animations, &PlayerActions), With<Player>>,
 ) {
-    if input.just_pressed(KeyCode::T) {
-        if let Ok(mut animation) = player_anim.get_single_mut() {
+    for (mut animation, actions) in player_anim.iter_mut() {
+        if actions.talk {
             animation.current_animation = 1;
             dbg!(animation);
         }
     }
 }
 
+/// World position of an LDtk entity and the level entity it belongs to, added up from its parents.
+/// Levels have only just spawned when the players do, so `GlobalTransform` isn't there yet.
+fn level_position(
+    entity: Entity,
+    transform_query: &Query<(&Transform, Option<&Parent>)>,
+    level_query: &Query<(Entity, &Handle<LdtkLevel>)>,
+) -> Option<(Vec3, Entity)> {
+    let mut position = Vec3::ZERO;
+    let mut current = entity;
+    loop {
+        let (transform, parent) = transform_query.get(current).ok()?;
+        position += transform.translation;
+        if level_query.contains(current) {
+            return Some((position, current));
+        }
+        current = parent?.get();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_player(
     mut commands: Commands,
-    _ev_asset: EventReader<AssetEvent<Image>>,
-    _asset_server: Res<AssetServer>,
-    spawn_query: Query<&GridCoords, With<Spawn>>,
+    player_spawn_query: Query<(Entity, &PlayerSpawn)>,
+    transform_query: Query<(&Transform, Option<&Parent>)>,
+    level_query: Query<(Entity, &Handle<LdtkLevel>)>,
+    world_query: Query<&Handle<LdtkAsset>>,
+    ldtk_levels: Res<Assets<LdtkLevel>>,
+    ldtk_assets: Res<Assets<LdtkAsset>>,
+    level_selection: Res<LevelSelection>,
     player_query: Query<Entity, With<Player>>,
-    _assets: Res<Assets<Image>>,
+    gamepads: Res<Gamepads>,
     characters: Res<CharacterSheet>,
+    mut player_spawns: ResMut<PlayerSpawns>,
+    mut restored_spawns: ResMut<RestoredSpawns>,
 ) {
-    if player_query.is_empty() {
-        println!("Creating players");
-        println!("{:?}", spawn_query);
-
-        spawn_query.for_each(|cords| {
-            println!("1Spawning player at cords: x:{}, y:{}", cords.x, cords.y);
-            let mut x: f32 = cords.x as f32 * 16.0;
-            let mut y: f32 = cords.y as f32 * 16.0;
-            x += 8.0;
-            y += 8.0;
-            y *= 2f32;
-            commands
-                .spawn(PlayerBundle {
-                    sprite_bundle: SpriteSheetBundle {
-                        transform: Transform::from_xyz(
-                            x,
-                            y,
-                            0.0,
-                        ),
-                        sprite: TextureAtlasSprite::new(characters.run_animation[0]),
-                        texture_atlas: characters.handle.clone(),
-                        visibility: Visibility::Visible,
-                        ..default()
-                    },
-                    collider_bundle: ColliderBundle {
-                        collider: Collider::cuboid(5.0, 5.0),
-                        rigid_body: RigidBody::Dynamic,
-                        friction: Friction {
-                            coefficient: 0.0,
-                            combine_rule: CoefficientCombineRule::Min,
-                        },
-                        rotation_constraints: LockedAxes::ROTATION_LOCKED,
-                        ..Default::default()
-                    },
-                    weapon: Weapon {
-                        reloading: false,
-                        mag_size: 30,
-                        ammo: Ammo{
-                            bullets: 0,
-                        },
-                        ..Default::default()
-                    },
-                    health: Health{
-                        health_points: 10
-                    },
-                    ..Default::default()
-                })
-                .insert(Animations {
-                    animations: vec![
-                        FrameAnimation {
-                            timer: Timer::from_seconds(0.2, TimerMode::Repeating),
-                            frames: characters.run_animation.to_vec(),
-                            current_frame: 0,
-                        },
-                        FrameAnimation {
-                            timer: Timer::from_seconds(0.4, TimerMode::Repeating),
-                            frames: characters.talk_animation.to_vec(),
-                            current_frame: 0,
-                        },
-                        FrameAnimation {
-                            timer: Timer::from_seconds(0.05, TimerMode::Repeating),
-                            frames: characters.shoot.to_vec(),
-                            current_frame: 0,
-                        },
-                        FrameAnimation {
-                            timer: Timer::from_seconds(0.4, TimerMode::Once),
-                            frames: characters.idle.to_vec(),
-                            current_frame: 0,
-                        }
-                    ],
-                    current_animation: 0,
-                })
-                .insert(ActiveEvents::COLLISION_EVENTS);
-        });
+    if !player_query.is_empty() {
+        return;
+    }
+
+    // Keyboard and mouse is always player one, every connected gamepad gets its own player.
+    let devices = std::iter::once(InputDevice::KeyboardMouse)
+        .chain(gamepads.iter().map(InputDevice::Gamepad));
+
+    // A loaded game puts the players back where they were saved
+    if !restored_spawns.points.is_empty() {
+        println!("Restoring players");
+        player_spawns.points = std::mem::take(&mut restored_spawns.points);
+        for (slot, device) in devices.enumerate() {
+            let Some(spawn_point) = player_spawns.points.get(&PlayerId(slot)) else {
+                continue;
+            };
+            spawn_player_at(&mut commands, &characters, spawn_point, PlayerId(slot), device);
+        }
+        return;
     }
-}
\ No newline at end of file
+
+    // Neighbor levels are loaded too, only the spawns of the selected level count
+    let project_levels: Vec<&String> = world_query
+        .iter()
+        .filter_map(|world_handle| ldtk_assets.get(world_handle))
+        .flat_map(|ldtk_asset| ldtk_asset.project.levels.iter().map(|level| &level.iid))
+        .collect();
+    let selected_level = level_query
+        .iter()
+        .filter_map(|(level_entity, level_handle)| {
+            ldtk_levels.get(level_handle).map(|ldtk_level| (level_entity, &ldtk_level.level))
+        })
+        .find(|(_, level)| {
+            let index = project_levels
+                .iter()
+                .position(|iid| **iid == level.iid)
+                .unwrap_or(usize::MAX);
+            level_selection.is_match(&index, level)
+        })
+        .map(|(level_entity, _)| level_entity);
+
+    let spawns: Vec<(Vec3, &PlayerSpawn)> = player_spawn_query
+        .iter()
+        .filter_map(|(spawn_entity, player_spawn)| {
+            let (position, level) = level_position(spawn_entity, &transform_query, &level_query)?;
+            let in_level = selected_level.map_or(true, |selected| selected == level);
+            in_level.then_some((position.truncate().extend(0.0), player_spawn))
+        })
+        .collect();
+    if spawns.is_empty() {
+        println!("There is no PlayerSpawn in the level");
+        return;
+    }
+
+    println!("Creating players");
+    player_spawns.points.clear();
+    let shared: Vec<&(Vec3, &PlayerSpawn)> = spawns
+        .iter()
+        .filter(|(_, player_spawn)| player_spawn.slot.is_none())
+        .collect();
+    let mut sharing = 0;
+
+    // A slot uses the spawn set up for it, or else one left for any slot.
+    // Players sharing a spawn are lined up next to each other.
+    for (slot, device) in devices.enumerate() {
+        let own_spawn = spawns
+            .iter()
+            .find(|(_, player_spawn)| player_spawn.slot == Some(slot));
+        let (position, player_spawn) = match own_spawn {
+            Some((position, player_spawn)) => (*position, *player_spawn),
+            None if !shared.is_empty() => {
+                let (position, player_spawn) = shared[sharing % shared.len()];
+                let offset = Vec3::new((sharing / shared.len()) as f32 * 16.0, 0.0, 0.0);
+                sharing += 1;
+                (*position + offset, *player_spawn)
+            }
+            None => {
+                println!("There is no PlayerSpawn for player {}", slot + 1);
+                continue;
+            }
+        };
+
+        let spawn_point = SpawnPoint {
+            position,
+            facing_left: player_spawn.facing_left,
+            loadout: player_spawn.loadout,
+        };
+        spawn_player_at(&mut commands, &characters, &spawn_point, PlayerId(slot), device);
+        player_spawns.points.insert(PlayerId(slot), spawn_point);
+    }
+}
+
+/// Lets a gamepad that isn't controlling anybody join a running game by pressing start.
+pub fn join_players(
+    mut commands: Commands,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    player_query: Query<(&Transform, &PlayerId, &InputDevice), With<Player>>,
+    characters: Res<CharacterSheet>,
+) {
+    for gamepad in gamepads.iter() {
+        if !gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start)) {
+            continue;
+        }
+        if player_query.iter().any(|(_, _, device)| *device == InputDevice::Gamepad(gamepad)) {
+            continue;
+        }
+        let Some((leader, _, _)) = player_query.iter().min_by_key(|(_, id, _)| id.0) else {
+            continue;
+        };
+
+        let mut slot = 0;
+        while player_query.iter().any(|(_, id, _)| id.0 == slot) {
+            slot += 1;
+        }
+        println!("Player {} joined", slot + 1);
+        spawn_player_entity(
+            &mut commands,
+            &characters,
+            leader.translation + Vec3::new(16.0, 0.0, 0.0),
+            PlayerId(slot),
+            InputDevice::Gamepad(gamepad),
+        );
+    }
+}
+
+/// Spawns a player at `spawn_point`, facing its way and with its loadout
+pub fn spawn_player_at(
+    commands: &mut Commands,
+    characters: &CharacterSheet,
+    spawn_point: &SpawnPoint,
+    player_id: PlayerId,
+    device: InputDevice,
+) -> Entity {
+    let loadout = spawn_point.loadout;
+    let player = spawn_player_entity(commands, characters, spawn_point.position, player_id, device);
+    commands
+        .entity(player)
+        .insert(TextureAtlasSprite {
+            index: characters.run_animation[0],
+            flip_x: spawn_point.facing_left,
+            ..default()
+        })
+        .insert(Health {
+            health_points: loadout.health,
+        })
+        .insert(MaxHealth {
+            health_points: loadout.health,
+        })
+        .insert(Weapon {
+            mag_size: loadout.mag_size,
+            ammo: Ammo {
+                bullets: loadout.bullets.min(loadout.mag_size),
+            },
+            ..Default::default()
+        })
+        .insert(Inventory {
+            grenades: loadout.grenades,
+            ..Default::default()
+        });
+    player
+}
+
+pub fn spawn_player_entity(
+    commands: &mut Commands,
+    characters: &CharacterSheet,
+    translation: Vec3,
+    player_id: PlayerId,
+    device: InputDevice,
+) -> Entity {
+    commands
+        .spawn(PlayerBundle {
+            sprite_bundle: SpriteSheetBundle {
+                transform: Transform::from_translation(translation),
+                sprite: TextureAtlasSprite::new(characters.run_animation[0]),
+                texture_atlas: characters.handle.clone(),
+                visibility: Visibility::Visible,
+                ..default()
+            },
+            collider_bundle: ColliderBundle {
+                collider: Collider::cuboid(5.0, 5.0),
+                rigid_body: RigidBody::Dynamic,
+                friction: Friction {
+                    coefficient: 0.0,
+                    combine_rule: CoefficientCombineRule::Min,
+                },
+                rotation_constraints: LockedAxes::ROTATION_LOCKED,
+                ..Default::default()
+            },
+            weapon: Weapon {
+                reloading: false,
+                mag_size: 30,
+                ammo: Ammo{
+                    bullets: 0,
+                },
+                ..Default::default()
+            },
+            health: Health{
+                health_points: PLAYER_HEALTH
+            },
+            player_id,
+            input_device: device,
+            ..Default::default()
+        })
+        .insert(characters.animations())
+        .insert(ActiveEvents::COLLISION_EVENTS)
+        .id()
+}
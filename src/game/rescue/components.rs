@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+/// Survivor that agreed to come along, follows `leader` around
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Component)]
+pub struct Follower {
+    pub leader: Entity,
+}
+
+/// Area placed in LDtk, survivors brought inside it are rescued
+#[derive(Copy, Clone, PartialEq, Debug, Default, Component)]
+pub struct Extraction {
+    pub size: Vec2,
+}
+
+impl From<&EntityInstance> for Extraction {
+    fn from(entity_instance: &EntityInstance) -> Extraction {
+        Extraction {
+            size: Vec2::new(entity_instance.width as f32, entity_instance.height as f32),
+        }
+    }
+}
+
+#[derive(Clone, Default, Bundle, LdtkEntity)]
+pub struct ExtractionBundle {
+    #[bundle]
+    pub sprite_bundle: SpriteBundle,
+    #[from_entity_instance]
+    pub extraction: Extraction,
+}
+
+/// Sent when a survivor reaches an extraction zone
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SurvivorRescued;
+
+/// Survivors brought to an extraction zone and survivors the zombies got during this run
+#[derive(Resource, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct RescueObjective {
+    pub rescued: u32,
+    pub lost: u32,
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+mod systems;
+pub mod components;
+
+use components::{ExtractionBundle, RescueObjective, SurvivorRescued};
+use systems::*;
+
+use crate::{AppState, FixedGameplaySet, SimulationState};
+use crate::game::npcs::systems::advance_dialogue;
+
+pub struct RescuePlugin;
+
+impl Plugin for RescuePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RescueObjective>()
+            .add_event::<SurvivorRescued>()
+            .register_ldtk_entity::<ExtractionBundle>("Extraction")
+            .add_system(setup_extractions)
+            .add_systems(
+                (
+                    start_following.after(advance_dialogue),
+                    extract_survivors,
+                )
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            )
+            .add_systems(
+                (
+                    survivor_follow,
+                    survivor_take_dmg,
+                    survivor_death.after(survivor_take_dmg),
+                )
+                .in_set(FixedGameplaySet)
+                .in_schedule(CoreSchedule::FixedUpdate)
+            )
+            .add_system(reset_rescue_objective.in_schedule(OnExit(AppState::MainMenu)))
+            .add_system(reset_rescue_objective.in_schedule(OnExit(AppState::GameOver)));
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+use crate::components::Health;
+use crate::game::enemies::components::Zombie;
+use crate::game::npcs::components::{DialogueEvent, Npc};
+use crate::game::npcs::dialogue::DialogueAction;
+use crate::game::player::components::Player;
+
+const SURVIVOR_HEALTH: i32 = 4;
+const SURVIVOR_SPEED: f32 = 110.0;
+/// Distance a survivor keeps from whoever it follows
+const SURVIVOR_SPACING: f32 = 20.0;
+
+pub fn setup_extractions(
+    mut commands: Commands,
+    extraction_query: Query<(Entity, &Extraction), Added<Extraction>>,
+) {
+    for (extraction_entity, extraction) in extraction_query.iter() {
+        commands
+            .entity(extraction_entity)
+            .insert(Sprite {
+                color: Color::rgba(0.24, 0.82, 0.44, 0.2),
+                custom_size: Some(extraction.size),
+                ..Default::default()
+            })
+            .insert(DEFAULT_IMAGE_HANDLE.typed::<Image>());
+    }
+}
+
+pub fn start_following(
+    mut commands: Commands,
+    mut dialogue_events: EventReader<DialogueEvent>,
+    npc_query: Query<(&Npc, &GlobalTransform, &EntityInstance), Without<Follower>>,
+    world_query: Query<(Entity, &GlobalTransform), With<Handle<LdtkAsset>>>,
+) {
+    for event in dialogue_events.iter() {
+        if event.action != DialogueAction::Follow {
+            continue;
+        }
+        let Ok((npc, npc_transform, entity_instance)) = npc_query.get(event.npc) else {
+            continue;
+        };
+
+        println!("{} is following you", npc.name);
+        // Moves from its level to the world, so it stays when the level unloads and can follow
+        // the player to other levels. The level doesn't spawn it again when it comes back.
+        if let Ok((world, world_transform)) = world_query.get_single() {
+            commands
+                .entity(event.npc)
+                .insert(Transform::from_translation(npc_transform.translation() - world_transform.translation()))
+                .insert(Worldly::from_entity_info(entity_instance))
+                .set_parent(world);
+        }
+        // Npcs stand still as fixed bodies, a follower has to be pushed around like the buddy
+        commands
+            .entity(event.npc)
+            .insert(Follower { leader: event.player })
+            .insert(Health {
+                health_points: SURVIVOR_HEALTH,
+            })
+            .insert(RigidBody::Dynamic)
+            .insert(LockedAxes::ROTATION_LOCKED)
+            .insert(Friction {
+                coefficient: 0.0,
+                combine_rule: CoefficientCombineRule::Min,
+            })
+            .insert(ActiveEvents::COLLISION_EVENTS);
+    }
+}
+
+pub fn survivor_follow(
+    mut survivor_query: Query<(&mut Follower, &Transform, &mut Velocity, &mut TextureAtlasSprite)>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+) {
+    for (mut follower, transform, mut velocity, mut sprite) in survivor_query.iter_mut() {
+        // The leader died or left, stick with the nearest player instead
+        if player_query.get(follower.leader).is_err() {
+            let nearest_player = player_query.iter().min_by(|(_, a), (_, b)| {
+                a.translation.distance_squared(transform.translation)
+                    .total_cmp(&b.translation.distance_squared(transform.translation))
+            });
+            match nearest_player {
+                Some((player, _)) => follower.leader = player,
+                None => {
+                    velocity.linvel = Vec2::ZERO;
+                    continue;
+                }
+            }
+        }
+
+        let Ok((_, leader_transform)) = player_query.get(follower.leader) else {
+            continue;
+        };
+        let to_leader = (leader_transform.translation - transform.translation).truncate();
+        if to_leader.length() > SURVIVOR_SPACING {
+            velocity.linvel = to_leader.normalize() * SURVIVOR_SPEED;
+            sprite.flip_x = to_leader.x < 0.0;
+        } else {
+            velocity.linvel = Vec2::ZERO;
+        }
+    }
+}
+
+pub fn survivor_take_dmg(
+    zombies: Query<Entity, With<Zombie>>,
+    mut survivor_query: Query<(Entity, &mut Health), With<Follower>>,
+    mut survivor_collisions: EventReader<CollisionEvent>,
+) {
+    for col_event in survivor_collisions.iter() {
+        if let CollisionEvent::Started(e1, e2, _) = col_event.to_owned() {
+            for (survivor, mut health) in survivor_query.iter_mut() {
+                let other = if survivor == e1 {
+                    e2
+                } else if survivor == e2 {
+                    e1
+                } else {
+                    continue;
+                };
+                if zombies.contains(other) {
+                    health.health_points -= 1;
+                }
+            }
+        }
+    }
+}
+
+pub fn survivor_death(
+    mut commands: Commands,
+    mut objective: ResMut<RescueObjective>,
+    survivor_query: Query<(Entity, &Npc, &Health), With<Follower>>,
+) {
+    for (survivor, npc, health) in survivor_query.iter() {
+        if health.health_points <= 0 {
+            println!("{} didn't make it", npc.name);
+            objective.lost += 1;
+            commands.entity(survivor).despawn_recursive();
+        }
+    }
+}
+
+pub fn extract_survivors(
+    mut commands: Commands,
+    mut objective: ResMut<RescueObjective>,
+    extraction_query: Query<(&GlobalTransform, &Extraction)>,
+    survivor_query: Query<(Entity, &GlobalTransform, &Npc), With<Follower>>,
+    mut rescued_events: EventWriter<SurvivorRescued>,
+) {
+    for (survivor, survivor_transform, npc) in survivor_query.iter() {
+        let position = survivor_transform.translation().truncate();
+        let extracted = extraction_query.iter().any(|(transform, extraction)| {
+            let offset = (position - transform.translation().truncate()).abs();
+            offset.x <= extraction.size.x / 2.0 && offset.y <= extraction.size.y / 2.0
+        });
+        if extracted {
+            objective.rescued += 1;
+            println!("{} was rescued ({} so far)", npc.name, objective.rescued);
+            rescued_events.send(SurvivorRescued);
+            commands.entity(survivor).despawn_recursive();
+        }
+    }
+}
+
+pub fn reset_rescue_objective(
+    mut objective: ResMut<RescueObjective>,
+) {
+    *objective = RescueObjective::default();
+}
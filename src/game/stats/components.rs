@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Numbers shown on the Game Over screen, collected over the whole run
+#[derive(Resource, Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    pub gun_kills: u32,
+    pub grenade_kills: u32,
+    pub shots: u32,
+    pub hits: u32,
+    pub grenades_thrown: u32,
+    /// Health points lost by all players together
+    pub damage_taken: u32,
+    /// Seconds spent in game, loading screens not included
+    pub time_survived: f32,
+    /// Levels entered, the one the run started in included
+    pub levels_reached: u32,
+}
+
+impl RunStats {
+    pub fn kills(&self) -> u32 {
+        self.gun_kills + self.grenade_kills
+    }
+
+    /// Share of fired bullets that hit a zombie, in percent
+    pub fn accuracy(&self) -> f32 {
+        if self.shots == 0 {
+            0.0
+        } else {
+            (self.hits as f32 / self.shots as f32) * 100.0
+        }
+    }
+
+    /// Ranks runs on the leaderboard, kills and levels count the most
+    pub fn score(&self) -> u32 {
+        self.kills() * 100 + self.levels_reached * 500 + self.time_survived as u32
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+mod systems;
+pub mod components;
+
+use components::RunStats;
+use systems::*;
+
+use crate::{AppState, HostGameplaySet, SimulationState};
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .add_system(
+                count_run_stats
+                    .in_set(HostGameplaySet)
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(SimulationState::Running))
+            )
+            .add_system(reset_run_stats.in_schedule(OnExit(AppState::MainMenu)))
+            .add_system(reset_run_stats.in_schedule(OnExit(AppState::GameOver)));
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use super::components::RunStats;
+use crate::events::{BulletHit, LevelEntered};
+use crate::game::enemies::components::{DamageSource, ZombieKilled};
+use crate::game::player::components::{GrenadeThrown, PlayerHurt, ShotFired};
+
+#[allow(clippy::too_many_arguments)]
+pub fn count_run_stats(
+    mut stats: ResMut<RunStats>,
+    time: Res<Time>,
+    mut killed_events: EventReader<ZombieKilled>,
+    mut shot_events: EventReader<ShotFired>,
+    mut hit_events: EventReader<BulletHit>,
+    mut thrown_events: EventReader<GrenadeThrown>,
+    mut hurt_events: EventReader<PlayerHurt>,
+    mut entered_events: EventReader<LevelEntered>,
+) {
+    stats.time_survived += time.delta_seconds();
+    for event in killed_events.iter() {
+        match event.source {
+            DamageSource::Gun => stats.gun_kills += 1,
+            DamageSource::Grenade => stats.grenade_kills += 1,
+        }
+    }
+    stats.shots += shot_events.iter().count() as u32;
+    stats.hits += hit_events.iter().count() as u32;
+    stats.grenades_thrown += thrown_events.iter().count() as u32;
+    stats.damage_taken += hurt_events.iter().map(|event| event.damage.max(0) as u32).sum::<u32>();
+    stats.levels_reached += entered_events.iter().count() as u32;
+}
+
+pub fn reset_run_stats(
+    mut stats: ResMut<RunStats>,
+) {
+    *stats = RunStats::default();
+}
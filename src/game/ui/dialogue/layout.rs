@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::components::StateScoped;
+use crate::game::npcs::components::{DialogueState, Npc};
+use crate::game::npcs::dialogue::{Dialogue, DialogueNode};
+use crate::game::ui::dialogue::*;
+use crate::game::ui::dialogue::styles::*;
+
+pub fn update_dialogue_box(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    dialogue_state: Res<DialogueState>,
+    dialogues: Res<Assets<Dialogue>>,
+    npc_query: Query<&Npc>,
+    dialogue_box_query: Query<Entity, With<DialogueBox>>,
+) {
+    if !dialogue_state.is_changed() {
+        return;
+    }
+
+    for dialogue_box in dialogue_box_query.iter() {
+        commands.entity(dialogue_box).despawn_recursive();
+    }
+
+    let Some(active) = dialogue_state.active.as_ref() else {
+        return;
+    };
+    let Some(node) = dialogues
+        .get(&active.dialogue)
+        .and_then(|dialogue| dialogue.nodes.get(&active.node)) else {
+        return;
+    };
+    let speaker = node
+        .speaker
+        .clone()
+        .or_else(|| npc_query.get(active.npc).ok().map(|npc| npc.name.clone()))
+        .unwrap_or_default();
+
+    build_dialogue_box(&mut commands, &asset_server, &speaker, node, active.selected);
+}
+
+pub fn build_dialogue_box(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    speaker: &str,
+    node: &DialogueNode,
+    selected: usize,
+) -> Entity {
+    commands.spawn(
+        (NodeBundle {
+            style: DIALOGUE_BOX_STYLE,
+            background_color: DIALOGUE_BACKGROUND_COLOR.into(),
+            ..default()
+        },
+        DialogueBox{},
+        StateScoped(AppState::Game),
+    )).with_children(|parent| {
+        parent.spawn(
+            TextBundle::from_section(speaker, get_speaker_text_style(asset_server))
+        );
+        parent.spawn(
+            TextBundle::from_section(node.text.as_str(), get_dialogue_text_style(asset_server))
+        );
+
+        let choices: Vec<&str> = if node.choices.is_empty() {
+            vec!["Continue"]
+        } else {
+            node.choices.iter().map(|choice| choice.text.as_str()).collect()
+        };
+        for (index, text) in choices.into_iter().enumerate() {
+            let color = if index == selected { SELECTED_CHOICE_COLOR } else { NORMAL_CHOICE_COLOR };
+            parent.spawn(
+                (ButtonBundle {
+                    style: CHOICE_BUTTON_STYLE,
+                    background_color: color.into(),
+                    ..default()
+                },
+                DialogueChoiceButton { index },
+            )).with_children(|parent| {
+                parent.spawn(
+                    TextBundle::from_section(text, get_dialogue_text_style(asset_server))
+                );
+            });
+        }
+    }).id()
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+mod layout;
+mod styles;
+mod systems;
+
+use crate::{AppState, SimulationState};
+use crate::game::ui::dialogue::layout::*;
+use super::dialogue::systems::*;
+
+#[derive(Component)]
+pub struct DialogueBox {}
+
+/// Clickable dialogue choice, `index` into the choices of the current node
+#[derive(Component)]
+pub struct DialogueChoiceButton {
+    pub index: usize,
+}
+
+pub struct DialogueUIPlugin;
+
+impl Plugin for DialogueUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                (
+                    update_dialogue_box,
+                    interact_with_choice_buttons,
+                )
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+            );
+    }
+}
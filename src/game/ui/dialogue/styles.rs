@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+pub const DIALOGUE_BACKGROUND_COLOR: Color = Color::rgba(0.05, 0.05, 0.08, 0.85);
+pub const NORMAL_CHOICE_COLOR: Color = Color::rgba(0.15, 0.15, 0.15, 0.0);
+pub const SELECTED_CHOICE_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const PRESSED_CHOICE_COLOR: Color = Color::rgb(0.35, 0.75, 0.35);
+
+pub const DIALOGUE_BOX_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        left: Val::Percent(15.0),
+        right: Val::Percent(15.0),
+        top: Val::Px(24.0),
+        bottom: Val::Auto,
+    },
+    flex_direction: FlexDirection::Column,
+    padding: UiRect::all(Val::Px(16.0)),
+    gap: Size::new(Val::Px(0.0), Val::Px(6.0)),
+    ..Style::DEFAULT
+};
+
+pub const CHOICE_BUTTON_STYLE: Style = Style {
+    padding: UiRect::new(Val::Px(12.0), Val::Px(12.0), Val::Px(2.0), Val::Px(2.0)),
+    ..Style::DEFAULT
+};
+
+pub fn get_speaker_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 26.0,
+        color: Color::rgb(0.37, 0.77, 0.91),
+    }
+}
+
+pub fn get_dialogue_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 24.0,
+        color: Color::WHITE,
+    }
+}
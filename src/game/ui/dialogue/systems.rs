@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::game::npcs::components::{DialogueChoiceEvent, DialogueState};
+use crate::game::ui::dialogue::*;
+use crate::game::ui::dialogue::styles::*;
+
+pub fn interact_with_choice_buttons(
+    dialogue_state: Res<DialogueState>,
+    mut choice_events: EventWriter<DialogueChoiceEvent>,
+    mut button_query: Query<
+        (&Interaction, &DialogueChoiceButton, &mut BackgroundColor),
+        Changed<Interaction>
+    >,
+) {
+    let selected = dialogue_state.active.as_ref().map(|active| active.selected);
+    for (interaction, button, mut background_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_CHOICE_COLOR.into();
+                choice_events.send(DialogueChoiceEvent { index: button.index });
+            }
+            Interaction::Hovered => {
+                *background_color = SELECTED_CHOICE_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = if selected == Some(button.index) {
+                    SELECTED_CHOICE_COLOR.into()
+                } else {
+                    NORMAL_CHOICE_COLOR.into()
+                };
+            }
+        }
+    }
+}
@@ -1,105 +1,116 @@
 use bevy::prelude::*;
 
+use crate::AppState;
+use crate::components::StateScoped;
+use crate::game::player::components::{Player, PlayerId};
 use crate::game::ui::hud::*;
+use crate::game::ui::hud::styles::*;
 
 pub fn spawn_hud(
     mut commands: Commands,
-    asset_server: Res<AssetServer>
+    asset_server: Res<AssetServer>,
 ) {
-    let hud_entity = build_hud(&mut commands, &asset_server);
+    build_hud(&mut commands, &asset_server);
 }
 
 pub fn build_hud(
-    commands: &mut Commands, 
-    asset_server: &Res<AssetServer>
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
 ) -> Entity {
-    let hud_entity = commands.spawn(
+    commands.spawn(
         (NodeBundle {
-            style: Style{
-                flex_direction: FlexDirection::Row,
-                justify_content: JustifyContent::Start,
-                align_items: AlignItems::Start,
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                margin: UiRect::new(Val::Px(18.0), Val::Px(18.0), Val::Px(18.0), Val::Px(18.0)),
-                ..default()
-            },
+            style: HUD_STYLE,
             ..default()
         },
         Hud{},
-    )).with_children(|parent|{
+        StateScoped(AppState::Game),
+    )).with_children(|parent| {
         parent.spawn(
-            TextBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    position: UiRect {
-                        bottom: Val::Px(30.),
-                        right: Val::Px(70.),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                text: Text {
-                    sections: vec![
-                        TextSection::new(
-                            "Ammo: 0",
-                            TextStyle {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf").into(),
-                                font_size: 32.0,
-                                color: Color::WHITE,
-                            }
-                        )
-                    ],
-                    alignment: TextAlignment::Left,
-                    ..default()
-                },
+            (NodeBundle {
+                style: OBJECTIVE_LIST_STYLE,
                 ..default()
-            }
-        ).insert(AmmoCountText);
+            },
+            ObjectiveList{},
+        ));
+        parent.spawn((
+            TextBundle::from_section(
+                "Lives: 0",
+                get_hud_text_style(asset_server),
+            ).with_style(LIVES_STYLE),
+            LivesText,
+        ));
+    }).id()
+}
+
+pub fn spawn_player_panels(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    hud_query: Query<Entity, With<Hud>>,
+    player_query: Query<&PlayerId, (With<Player>, Added<PlayerId>)>,
+) {
+    let Ok(hud_entity) = hud_query.get_single() else {
+        return;
+    };
+
+    for player_id in player_query.iter() {
+        let panel = build_player_panel(&mut commands, &asset_server, *player_id);
+        commands.entity(hud_entity).add_child(panel);
+    }
+}
+
+pub fn despawn_player_panels(
+    mut commands: Commands,
+    panel_query: Query<(Entity, &PlayerId), With<PlayerPanel>>,
+    player_query: Query<&PlayerId, With<Player>>,
+) {
+    for (panel, panel_player) in panel_query.iter() {
+        if !player_query.iter().any(|player_id| player_id == panel_player) {
+            commands.entity(panel).despawn_recursive();
+        }
+    }
+}
+
+pub fn build_player_panel(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    player_id: PlayerId,
+) -> Entity {
+    commands.spawn(
+        (NodeBundle {
+            style: PLAYER_PANEL_STYLE,
+            ..default()
+        },
+        PlayerPanel{},
+        player_id,
+    )).with_children(|parent|{
         parent.spawn(
-            TextBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    position: UiRect {
-                        bottom: Val::Px(30.),
-                        left: Val::Px(10.),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                text: Text {
-                    sections: vec![
-                        TextSection::new(
-                            "Health: 0",
-                            TextStyle {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf").into(),
-                                font_size: 32.0,
-                                color: Color::WHITE,
-                            }
-                        )
-                    ],
-                    alignment: TextAlignment::Left,
-                    ..default()
-                },
-                ..default()
-            }
-        ).insert(HealthCountText);
+            TextBundle::from_section(
+                format!("P{}", player_id.0 + 1),
+                get_hud_text_style(asset_server),
+            )
+        );
+        parent.spawn((
+            TextBundle::from_section(
+                "Health: 0",
+                get_hud_text_style(asset_server),
+            ),
+            HealthCountText,
+            player_id,
+        ));
         parent.spawn(
             ImageBundle {
-                style: Style {
-                    size: Size::new(Val::Px(90.0),Val::Px(50.0)),
-                    position_type: PositionType::Absolute,
-                    position: UiRect {
-                        bottom: Val::Px(22.5),
-                        left: Val::Px(135.),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
+                style: OSCILLOSCOPE_STYLE,
                 image: asset_server.load("oscilloscope.png").into(),
                 ..default()
             },
         );
-
-    }).id();
-    hud_entity
-}
\ No newline at end of file
+        parent.spawn((
+            TextBundle::from_section(
+                "Ammo: 0",
+                get_hud_text_style(asset_server),
+            ),
+            AmmoCountText,
+            player_id,
+        ));
+    }).id()
+}
@@ -5,24 +5,49 @@ mod styles;
 mod systems;
 
 use crate::AppState;
-use crate::game::ui::hud::layout::spawn_hud;
+use crate::game::ui::hud::layout::*;
 use super::hud::systems::*;
 
 #[derive(Component)]
 pub struct Hud {}
 
+/// Per player part of the HUD, tagged with the `PlayerId` it shows
+#[derive(Component)]
+pub struct PlayerPanel {}
+
 #[derive(Component)]
 pub struct AmmoCountText;
 
 #[derive(Component)]
 pub struct HealthCountText;
+
+/// Objectives of the current level, one `ObjectiveText` per objective
+#[derive(Component)]
+pub struct ObjectiveList {}
+
+#[derive(Component)]
+pub struct ObjectiveText {
+    pub index: usize,
+}
+
+#[derive(Component)]
+pub struct LivesText;
+
 pub struct HudPlugin;
 
 impl Plugin for HudPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_system(spawn_hud.in_schedule(OnEnter(AppState::Game)))
-            .add_systems((update_ammo_text, update_health_text).in_set(OnUpdate(AppState::Game)))
-            .add_system(update_health_text.in_set(OnUpdate(AppState::Game)));
+            .add_systems(
+                (
+                    spawn_player_panels,
+                    despawn_player_panels,
+                    update_ammo_text,
+                    update_health_text,
+                    update_objective_list,
+                    update_lives_text,
+                ).in_set(OnUpdate(AppState::Game))
+            );
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+pub const HUD_STYLE: Style = Style {
+    flex_direction: FlexDirection::Row,
+    justify_content: JustifyContent::SpaceBetween,
+    align_items: AlignItems::End,
+    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+    padding: UiRect::all(Val::Px(18.0)),
+    ..Style::DEFAULT
+};
+
+pub const PLAYER_PANEL_STYLE: Style = Style {
+    flex_direction: FlexDirection::Row,
+    align_items: AlignItems::Center,
+    gap: Size::new(Val::Px(12.0), Val::Px(0.0)),
+    ..Style::DEFAULT
+};
+
+pub const OSCILLOSCOPE_STYLE: Style = Style {
+    size: Size::new(Val::Px(90.0), Val::Px(50.0)),
+    ..Style::DEFAULT
+};
+
+pub const OBJECTIVE_LIST_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        left: Val::Px(18.0),
+        top: Val::Px(18.0),
+        right: Val::Auto,
+        bottom: Val::Auto,
+    },
+    flex_direction: FlexDirection::Column,
+    gap: Size::new(Val::Px(0.0), Val::Px(4.0)),
+    ..Style::DEFAULT
+};
+
+pub const LIVES_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        left: Val::Auto,
+        top: Val::Px(18.0),
+        right: Val::Px(18.0),
+        bottom: Val::Auto,
+    },
+    ..Style::DEFAULT
+};
+
+pub const OBJECTIVE_COLOR: Color = Color::WHITE;
+pub const COMPLETED_OBJECTIVE_COLOR: Color = Color::rgb(0.35, 0.75, 0.35);
+
+pub fn get_hud_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 32.0,
+        color: Color::WHITE,
+    }
+}
+
+pub fn get_objective_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 22.0,
+        color: OBJECTIVE_COLOR,
+    }
+}
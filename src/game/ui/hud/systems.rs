@@ -1,27 +1,90 @@
 use bevy::prelude::*;
 
 use crate::components::{Weapon, Health};
-use crate::game::player::components::Player;
-use crate::game::ui::hud::{AmmoCountText, HealthCountText};
+use crate::game::objectives::components::{MissionTracker, Objective};
+use crate::game::player::components::{Player, PlayerId};
+use crate::game::player::resources::Lives;
+use crate::game::ui::hud::{AmmoCountText, HealthCountText, LivesText, ObjectiveList, ObjectiveText};
+use crate::game::ui::hud::styles::*;
 
 pub fn update_ammo_text(
-    weapon_query: Query<&Weapon, With<Player>>,
-    mut text_query: Query<&mut Text, With<AmmoCountText>>,
+    weapon_query: Query<(&Weapon, &PlayerId), With<Player>>,
+    mut text_query: Query<(&mut Text, &PlayerId), With<AmmoCountText>>,
 ) {
-    if let Ok(weapon) = weapon_query.get_single() {
-        if let Ok(mut text) = text_query.get_single_mut() {
-            text.sections[0].value = format!("Ammo: {}", weapon.ammo.bullets);
+    for (weapon, player_id) in weapon_query.iter() {
+        for (mut text, text_player) in text_query.iter_mut() {
+            if text_player == player_id {
+                text.sections[0].value = format!("Ammo: {}", weapon.ammo.bullets);
+            }
         }
     }
 }
 
 pub fn update_health_text(
-    health_query: Query<&Health, With<Player>>,
-    mut text_query: Query<&mut Text, With<HealthCountText>>,
+    health_query: Query<(&Health, &PlayerId), With<Player>>,
+    mut text_query: Query<(&mut Text, &PlayerId), With<HealthCountText>>,
 ) {
-    if let Ok(health) = health_query.get_single() {
-        if let Ok(mut text) = text_query.get_single_mut() {
-            text.sections[0].value = format!("Health: {}", health.health_points);
+    for (health, player_id) in health_query.iter() {
+        for (mut text, text_player) in text_query.iter_mut() {
+            if text_player == player_id {
+                text.sections[0].value = format!("Health: {}", health.health_points);
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+fn objective_color(objective: &Objective) -> Color {
+    if objective.completed() {
+        COMPLETED_OBJECTIVE_COLOR
+    } else {
+        OBJECTIVE_COLOR
+    }
+}
+
+pub fn update_objective_list(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    tracker: Res<MissionTracker>,
+    list_query: Query<Entity, With<ObjectiveList>>,
+    mut text_query: Query<(Entity, &ObjectiveText, &mut Text)>,
+) {
+    if !tracker.is_changed() {
+        return;
+    }
+    let Ok(list) = list_query.get_single() else {
+        return;
+    };
+    let objectives = tracker.objectives();
+
+    // Same number of lines, only the text changes
+    if text_query.iter().count() == objectives.len() {
+        for (_, objective_text, mut text) in text_query.iter_mut() {
+            let objective = &objectives[objective_text.index];
+            text.sections[0].value = objective.description();
+            text.sections[0].style.color = objective_color(objective);
+        }
+        return;
+    }
+
+    for (text_entity, _, _) in text_query.iter() {
+        commands.entity(text_entity).despawn_recursive();
+    }
+    for (index, objective) in objectives.iter().enumerate() {
+        let mut style = get_objective_text_style(&asset_server);
+        style.color = objective_color(objective);
+        let text = commands.spawn((
+            TextBundle::from_section(objective.description(), style),
+            ObjectiveText { index },
+        )).id();
+        commands.entity(list).add_child(text);
+    }
+}
+
+pub fn update_lives_text(
+    lives: Res<Lives>,
+    mut text_query: Query<&mut Text, With<LivesText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("Lives: {}", lives.remaining);
+    }
+}
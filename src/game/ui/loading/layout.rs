@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+use crate::game::ui::loading::LoadingScreen;
+use crate::game::ui::loading::styles::*;
+
+pub fn spawn_loading_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    build_loading_screen(&mut commands, &asset_server);
+}
+
+pub fn despawn_loading_screen(
+    mut commands: Commands,
+    loading_screen_query: Query<Entity, With<LoadingScreen>>,
+) {
+    for loading_screen in loading_screen_query.iter() {
+        commands.entity(loading_screen).despawn_recursive();
+    }
+}
+
+pub fn build_loading_screen(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
+    commands.spawn(
+        (NodeBundle {
+            style: LOADING_SCREEN_STYLE,
+            background_color: LOADING_BACKGROUND_COLOR.into(),
+            ..default()
+        },
+        LoadingScreen{},
+    )).with_children(|parent| {
+        parent.spawn(
+            TextBundle::from_section("Loading...", get_loading_text_style(asset_server))
+        );
+    }).id()
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+mod layout;
+mod styles;
+
+use crate::AppState;
+use crate::game::ui::loading::layout::*;
+
+#[derive(Component)]
+pub struct LoadingScreen {}
+
+pub struct LoadingUIPlugin;
+
+impl Plugin for LoadingUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_system(spawn_loading_screen.in_schedule(OnEnter(AppState::Loading)))
+            .add_system(despawn_loading_screen.in_schedule(OnExit(AppState::Loading)));
+    }
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+pub const LOADING_BACKGROUND_COLOR: Color = Color::rgb(0.05, 0.05, 0.08);
+
+pub const LOADING_SCREEN_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+    ..Style::DEFAULT
+};
+
+pub fn get_loading_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 48.0,
+        color: Color::WHITE,
+    }
+}
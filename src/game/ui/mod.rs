@@ -1,14 +1,20 @@
 use bevy::prelude::*;
 
+pub mod dialogue;
 pub mod hud;
+pub mod loading;
 
+pub use dialogue::DialogueUIPlugin;
 pub use hud::HudPlugin;
+pub use loading::LoadingUIPlugin;
 
 pub struct GameUIPlugin;
 
 impl Plugin for GameUIPlugin {
     fn build(&self, app: &mut App) {
         app
-        .add_plugin(HudPlugin);
+        .add_plugin(HudPlugin)
+        .add_plugin(DialogueUIPlugin)
+        .add_plugin(LoadingUIPlugin);
     }
 }
\ No newline at end of file
@@ -0,0 +1,10 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct GameOverMenu {}
+
+#[derive(Component)]
+pub struct RetryButton {}
+
+#[derive(Component)]
+pub struct MainMenuButton {}
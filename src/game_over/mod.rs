@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+mod components;
+mod styles;
+mod systems;
+
+use crate::AppState;
+
+use self::systems::{layout::{spawn_game_over_menu, despawn_game_over_menu}, interactions::{interact_with_retry_button, interact_with_main_menu_button}};
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        //OnEnter State Systems
+        .add_system(spawn_game_over_menu.in_schedule(OnEnter(AppState::GameOver)))
+        //Systems
+        .add_systems(
+            (
+                interact_with_retry_button,
+                interact_with_main_menu_button
+            ).in_set(OnUpdate(AppState::GameOver))
+        )
+        //OnExit State Systems
+        .add_system(despawn_game_over_menu.in_schedule(OnExit(AppState::GameOver)));
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game_over::components::*;
+use crate::game_over::styles::*;
+use crate::systems::ButtonInteraction;
+
+pub fn interact_with_retry_button(
+    mut button_query: ButtonInteraction<RetryButton>,
+    mut app_state_next_state: ResMut<NextState<AppState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                app_state_next_state.set(AppState::Loading);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_main_menu_button(
+    mut button_query: ButtonInteraction<MainMenuButton>,
+    mut app_state_next_state: ResMut<NextState<AppState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                app_state_next_state.set(AppState::MainMenu);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
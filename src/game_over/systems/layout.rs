@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+
+use crate::game::stats::components::RunStats;
+use crate::game_over::components::*;
+use crate::game_over::styles::*;
+
+pub fn spawn_game_over_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    stats: Res<RunStats>,
+) {
+    build_game_over_menu(&mut commands, &asset_server, &stats);
+}
+
+pub fn despawn_game_over_menu(
+    mut commands: Commands,
+    game_over_query: Query<Entity, With<GameOverMenu>>
+) {
+    if let Ok(game_over_entity) = game_over_query.get_single() {
+        commands.entity(game_over_entity).despawn_recursive();
+    }
+}
+
+fn stat_lines(stats: &RunStats) -> Vec<String> {
+    let seconds = stats.time_survived as u32;
+    vec![
+        format!("Score: {}", stats.score()),
+        format!("Kills: {} ({} by gun, {} by grenade)", stats.kills(), stats.gun_kills, stats.grenade_kills),
+        format!("Time survived: {}:{:02}", seconds / 60, seconds % 60),
+        format!("Accuracy: {:.0}% ({} of {} shots)", stats.accuracy(), stats.hits, stats.shots),
+        format!("Grenades thrown: {}", stats.grenades_thrown),
+        format!("Damage taken: {}", stats.damage_taken),
+        format!("Levels reached: {}", stats.levels_reached),
+    ]
+}
+
+pub fn build_game_over_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    stats: &RunStats,
+) -> Entity {
+    let game_over_entity = commands.spawn(
+        (
+            NodeBundle {
+            style: GAME_OVER_STYLE,
+            background_color: GAME_OVER_BACKGROUND_COLOR.into(),
+            ..default()
+        },
+        GameOverMenu{},
+    )
+    )
+    .with_children(|parent| {
+        //title
+        parent.spawn(
+            NodeBundle {
+                style: TITLE_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            parent.spawn(
+                TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Game Over",
+                                get_title_text_style(asset_server)
+                            )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //stats
+        parent.spawn(
+            NodeBundle {
+                style: STATS_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            for line in stat_lines(stats) {
+                parent.spawn(
+                    TextBundle::from_section(
+                        line,
+                        get_stat_text_style(asset_server),
+                    )
+                );
+            }
+        });
+        //retry button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: NORMAL_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                RetryButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Retry",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //main menu button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: NORMAL_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                MainMenuButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Main Menu",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+    })
+    .id();
+
+    game_over_entity
+}
@@ -0,0 +1,2 @@
+pub mod interactions;
+pub mod layout;
\ No newline at end of file
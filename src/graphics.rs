@@ -1,4 +1,4 @@
-use bevy::{prelude::*, reflect::Enum};
+use bevy::prelude::*;
 
 pub struct GraphicsPlugin;
 
@@ -25,6 +25,48 @@ pub struct FrameAnimation {
     pub current_frame: usize,
 }
 
+impl CharacterSheet {
+    /// The frames of the character sheet, drawn from the atlas `handle`
+    pub fn new(handle: Handle<TextureAtlas>) -> Self {
+        Self {
+            handle,
+            run_animation: [14*8, 14*8+1, 14*8+2, 14*8+3],
+            talk_animation: [10*8, 10*8+1, 10*8+2],
+            shoot: [25*8, 7*8+1],
+            idle: [7*8]
+        }
+    }
+
+    /// Run, talk, shoot and idle animations, in that order, for a character using this sheet
+    pub fn animations(&self) -> Animations {
+        Animations {
+            animations: vec![
+                FrameAnimation {
+                    timer: Timer::from_seconds(0.2, TimerMode::Repeating),
+                    frames: self.run_animation.to_vec(),
+                    current_frame: 0,
+                },
+                FrameAnimation {
+                    timer: Timer::from_seconds(0.4, TimerMode::Repeating),
+                    frames: self.talk_animation.to_vec(),
+                    current_frame: 0,
+                },
+                FrameAnimation {
+                    timer: Timer::from_seconds(0.05, TimerMode::Repeating),
+                    frames: self.shoot.to_vec(),
+                    current_frame: 0,
+                },
+                FrameAnimation {
+                    timer: Timer::from_seconds(0.4, TimerMode::Once),
+                    frames: self.idle.to_vec(),
+                    current_frame: 0,
+                }
+            ],
+            current_animation: 0,
+        }
+    }
+}
+
 impl Plugin for GraphicsPlugin {
     fn build(&self, app: &mut App){
         app.add_startup_system(Self::load_graphics).add_system(Self::frame_animation);
@@ -40,9 +82,9 @@ pub fn spawn_player_sprite(
     sprite.custom_size = Some(Vec2 { x: 32.0, y: 32.0 });
 
     commands.spawn(SpriteSheetBundle {
-        sprite: sprite,
+        sprite,
         texture_atlas: characters.handle.clone(), 
-        transform: Transform { translation: translation,
+        transform: Transform { translation,
         ..Default::default()
      },
      ..Default::default()
@@ -64,13 +106,7 @@ impl GraphicsPlugin {
         let atlas = TextureAtlas::from_grid(image, Vec2::splat(32.0), 8, 30, None, None);
         let atlas_handle = texture_atlases.add(atlas);
 
-        commands.insert_resource(CharacterSheet {
-            handle: atlas_handle,
-            run_animation: [14*8, 14*8+1, 14*8+2, 14*8+3],
-            talk_animation: [10*8, 10*8+1, 10*8+2],
-            shoot: [25*8, 7*8+1],
-            idle: [7*8]
-        });
+        commands.insert_resource(CharacterSheet::new(atlas_handle));
     }
 
     fn frame_animation(
@@ -0,0 +1,159 @@
+use std::env;
+use std::time::Duration;
+
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{AppState, SimulationState, FIXED_TIMESTEP};
+use crate::components::{ColliderBundle, Health, ZombieBundle};
+use crate::game::bot::BotPlugin;
+use crate::game::enemies::EnemyPlugin;
+use crate::game::npcs::components::DialogueState;
+use crate::game::player::PlayerPlugin;
+use crate::game::player::components::{InputDevice, Loadout, PlayerId};
+use crate::game::player::resources::{PlayerSpawns, SpawnPoint};
+use crate::game::player::systems::spawn_player_at;
+use crate::graphics::CharacterSheet;
+use crate::net::components::NetMode;
+use crate::replay::components::ReplayMode;
+use crate::resources::{LoadedLevels, MouseLoc};
+use crate::rollback::RollbackPlugin;
+use crate::settings::components::Settings;
+use crate::simulation::SimulationPlugin;
+
+pub mod soak;
+
+#[cfg(test)]
+mod tests;
+
+/// Half the width of the open square the headless app plays in, centered on the origin
+pub const ARENA_HALF_SIZE: f32 = 1000.0;
+
+/// `--headless <ticks>` soaks the simulation without a window for that many fixed steps, see `soak::run`
+pub fn ticks_from_args() -> Option<u32> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg != "--headless" {
+            continue;
+        }
+        match args.next().map(|ticks| ticks.parse()) {
+            Some(Ok(ticks)) => return Some(ticks),
+            _ => println!("--headless needs a number of ticks"),
+        }
+    }
+    None
+}
+
+/// The players, zombies and physics of the game without rendering, audio or levels.
+/// Assets still load from disk, but nothing draws them.
+/// Every `App::update` is exactly one fixed step.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(TransformPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(InputPlugin)
+        // The sprites and levels the game points at, so their handles behave like in the game
+        .add_asset::<Image>()
+        .add_asset::<TextureAtlas>()
+        .add_asset::<LdtkLevel>()
+        .add_asset::<LdtkAsset>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(FIXED_TIMESTEP)))
+        .add_state::<AppState>()
+        .add_state::<SimulationState>()
+        .insert_resource(Settings::default())
+        .insert_resource(MouseLoc{ loc: Vec2::ZERO })
+        .insert_resource(LevelSelection::Uid(0))
+        // No walls and no level changes, the whole arena counts as loaded
+        .insert_resource(LoadedLevels {
+            levels: vec![(
+                "headless".to_string(),
+                Rect::new(-ARENA_HALF_SIZE, -ARENA_HALF_SIZE, ARENA_HALF_SIZE, ARENA_HALF_SIZE),
+            )],
+        })
+        .insert_resource(CharacterSheet::new(Handle::default()))
+        .init_resource::<ReplayMode>()
+        .init_resource::<NetMode>()
+        .init_resource::<DialogueState>()
+        .add_plugin(SimulationPlugin)
+        .add_plugin(PlayerPlugin)
+        .add_plugin(EnemyPlugin)
+        .add_plugin(BotPlugin)
+        .add_plugin(RollbackPlugin);
+    app
+}
+
+/// Leaves the main menu for a game. There is no level, so nobody spawns on their own.
+pub fn start_game(app: &mut App) {
+    app.world.resource_mut::<NextState<AppState>>().set(AppState::Game);
+    app.update();
+}
+
+/// Steps the app `ticks` fixed steps, or until the game is lost. Returns the steps taken.
+pub fn run_ticks(app: &mut App, ticks: u32) -> u32 {
+    for tick in 1..=ticks {
+        app.update();
+        if app.world.resource::<State<AppState>>().0 == AppState::GameOver {
+            return tick;
+        }
+    }
+    ticks
+}
+
+/// Runs `spawn` with commands that are applied to the app's world right after
+pub fn with_commands<T>(app: &mut App, spawn: impl FnOnce(&mut Commands, &World) -> T) -> T {
+    let mut queue = CommandQueue::default();
+    let result = {
+        let mut commands = Commands::new(&mut queue, &app.world);
+        spawn(&mut commands, &app.world)
+    };
+    queue.apply(&mut app.world);
+    result
+}
+
+/// Spawns player one, played with `device`, at `position`, where it also respawns
+pub fn add_player(app: &mut App, position: Vec2, device: InputDevice) -> Entity {
+    let spawn_point = SpawnPoint {
+        position: position.extend(0.0),
+        facing_left: false,
+        loadout: Loadout::default(),
+    };
+    app.world
+        .resource_mut::<PlayerSpawns>()
+        .points
+        .insert(PlayerId(0), spawn_point.clone());
+    with_commands(app, |commands, world| {
+        spawn_player_at(
+            commands,
+            world.resource::<CharacterSheet>(),
+            &spawn_point,
+            PlayerId(0),
+            device,
+        )
+    })
+}
+
+pub fn add_zombie(app: &mut App, position: Vec2, health_points: i32) -> Entity {
+    let zombie = app.world.spawn_empty().id();
+    // Saves tell zombies apart by iid
+    let entity_instance = EntityInstance {
+        identifier: "Zombie".to_string(),
+        iid: format!("headless-zombie-{:?}", zombie),
+        ..default()
+    };
+    app.world.entity_mut(zombie).insert(ZombieBundle {
+        sprite_bundle: SpriteBundle {
+            transform: Transform::from_translation(position.extend(0.0)),
+            ..default()
+        },
+        collider_bundle: ColliderBundle::from(&entity_instance),
+        entity_instance,
+        health: Health { health_points },
+        ..default()
+    });
+    zombie
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::{AppState, FixedGameplaySet};
+use crate::components::Health;
+use crate::game::bot::components::BotRoute;
+use crate::game::enemies::components::{Zombie, ZOMBIE_CHASE_RANGE};
+use crate::game::player::components::{InputDevice, Player};
+use super::{add_player, add_zombie, headless_app, run_ticks, start_game};
+
+/// Zombies kept around the bot, topped up every `WAVE_TICKS`
+const SOAK_ZOMBIES: usize = 8;
+const WAVE_TICKS: u32 = 600;
+/// Distance from the origin new zombies show up at
+const WAVE_RADIUS: f32 = 220.0;
+/// A minute of game time between reports
+const REPORT_TICKS: u32 = 3600;
+/// Ticks a chasing zombie may stand on the same spot before it counts as stuck
+const STUCK_TICKS: u32 = 300;
+/// Zombies this close are at the player already and standing still is fine
+const CONTACT_RANGE: f32 = 40.0;
+/// More entities than this besides players and zombies, bullets and grenades mostly, means something isn't cleaned up
+const LEAK_LIMIT: usize = 500;
+
+/// Where every zombie last moved and for how many ticks it hasn't since, while it should have
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SoakWatch {
+    pub zombies: HashMap<Entity, (Vec2, u32)>,
+    pub stuck: usize,
+}
+
+pub fn watch_zombies(
+    mut watch: ResMut<SoakWatch>,
+    zombie_query: Query<(Entity, &Transform), With<Zombie>>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+) {
+    watch.zombies.retain(|zombie, _| zombie_query.contains(*zombie));
+    let mut stuck = 0;
+    for (zombie, zombie_transform) in zombie_query.iter() {
+        let position = zombie_transform.translation.truncate();
+        let (last_position, still_ticks) = watch.zombies.entry(zombie).or_insert((position, 0));
+        if position.distance(*last_position) > 1.0 {
+            *last_position = position;
+            *still_ticks = 0;
+            continue;
+        }
+
+        let chasing = player_query
+            .iter()
+            .filter(|(_, health)| health.health_points > 0)
+            .map(|(player_transform, _)| player_transform.translation.truncate().distance(position))
+            .any(|distance| distance > CONTACT_RANGE && distance < ZOMBIE_CHASE_RANGE);
+        if !chasing {
+            *still_ticks = 0;
+            continue;
+        }
+        *still_ticks += 1;
+        if *still_ticks == STUCK_TICKS {
+            println!("Zombie {:?} is stuck at {}", zombie, position);
+            stuck += 1;
+        }
+    }
+    watch.stuck += stuck;
+}
+
+fn add_bot(app: &mut App) {
+    add_player(app, Vec2::ZERO, InputDevice::Bot);
+}
+
+fn top_up_zombies(app: &mut App) {
+    let zombies = app.world.query_filtered::<(), With<Zombie>>().iter(&app.world).count();
+    for index in zombies..SOAK_ZOMBIES {
+        let angle = index as f32 / SOAK_ZOMBIES as f32 * std::f32::consts::TAU;
+        add_zombie(app, Vec2::from_angle(angle) * WAVE_RADIUS, Health::default().health_points);
+    }
+}
+
+/// Lets a bot fight waves of zombies for `ticks` fixed steps, starting over whenever the game is lost.
+/// Reports every minute of game time and tells whether it went without stuck zombies or leaked entities.
+/// A crash is a panic like anywhere else.
+pub fn run(ticks: u32) -> bool {
+    let mut app = headless_app();
+    app
+        .init_resource::<SoakWatch>()
+        .insert_resource(BotRoute {
+            waypoints: vec![
+                Vec2::new(-100.0, -100.0),
+                Vec2::new(100.0, -100.0),
+                Vec2::new(100.0, 100.0),
+                Vec2::new(-100.0, 100.0),
+            ],
+        })
+        .add_system(
+            watch_zombies
+                .in_set(FixedGameplaySet)
+                .in_schedule(CoreSchedule::FixedUpdate)
+        );
+    start_game(&mut app);
+    add_bot(&mut app);
+
+    println!("Soaking for {} ticks", ticks);
+    let mut games_lost = 0;
+    let mut leaking = false;
+    let mut tick = 0;
+    while tick < ticks {
+        if tick % WAVE_TICKS == 0 {
+            top_up_zombies(&mut app);
+        }
+        let next_wave = (tick / WAVE_TICKS + 1) * WAVE_TICKS;
+        let next_report = (tick / REPORT_TICKS + 1) * REPORT_TICKS;
+        tick += run_ticks(&mut app, next_wave.min(next_report).min(ticks) - tick);
+
+        if app.world.resource::<State<AppState>>().0 == AppState::GameOver {
+            games_lost += 1;
+            println!("Game {} lost at tick {}, starting over", games_lost, tick);
+            start_game(&mut app);
+            add_bot(&mut app);
+        }
+
+        if tick % REPORT_TICKS == 0 || tick == ticks {
+            let players = app.world.query_filtered::<(), With<Player>>().iter(&app.world).count();
+            let zombies = app.world.query_filtered::<(), With<Zombie>>().iter(&app.world).count();
+            let others = app.world.entities().len() as usize - players - zombies;
+            println!(
+                "Tick {}: {} players, {} zombies, {} other entities",
+                tick,
+                players,
+                zombies,
+                others
+            );
+            if others > LEAK_LIMIT {
+                println!("{} entities besides players and zombies, something isn't despawned", others);
+                leaking = true;
+            }
+        }
+    }
+
+    let stuck = app.world.resource::<SoakWatch>().stuck;
+    println!(
+        "Soaked {} ticks: {} games lost, {} stuck zombies{}",
+        ticks,
+        games_lost,
+        stuck,
+        if leaking { ", leaking entities" } else { "" }
+    );
+    stuck == 0 && !leaking
+}
@@ -0,0 +1,477 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::*;
+use crate::components::{DetonationTimer, Grenade, GrenadeBundle, Inventory, MaxHealth, Weapon};
+use crate::events::{LevelEntered, LevelExited};
+use crate::game::campaign::components::PlayerLoadout;
+use crate::game::enemies::components::Zombie;
+use crate::game::objectives::ObjectivePlugin;
+use crate::game::objectives::components::{LevelExit, MissionTracker, Objective, ObjectiveKind};
+use crate::game::pickups::components::{Pickup, PickupCollected, PickupKind};
+use crate::game::pickups::systems::apply_pickup;
+use crate::game::player::components::PlayerActions;
+use crate::game::player::input::read_player_input;
+use crate::game::player::resources::{Lives, Respawns};
+use crate::game::player::systems::spawn_bullet;
+use crate::game::rescue::components::SurvivorRescued;
+use crate::game::stats::components::RunStats;
+use crate::net::components::*;
+use crate::net::systems::host::{receive_client_messages, split_snapshot};
+use crate::replay::components::{Replay, ReplayCursor};
+use crate::replay::systems::{is_playing_back, is_recording, play_frame, record_frame};
+use crate::rollback::components::{RollbackId, SnapshotBuffer, WorldSnapshot};
+use crate::rollback::systems::rewind;
+use crate::save::components::SavedPlayer;
+
+fn position(app: &App, entity: Entity) -> Vec2 {
+    app.world.get::<Transform>(entity).unwrap().translation.truncate()
+}
+
+fn health(app: &App, entity: Entity) -> i32 {
+    app.world.get::<Health>(entity).unwrap().health_points
+}
+
+fn app_state(app: &App) -> AppState {
+    app.world.resource::<State<AppState>>().0
+}
+
+#[test]
+fn zombies_approach_the_player() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let player = add_player(&mut app, Vec2::ZERO, InputDevice::KeyboardMouse);
+    let zombie = add_zombie(&mut app, Vec2::new(100.0, 0.0), 2);
+    run_ticks(&mut app, 2);
+    let start = position(&app, zombie).distance(position(&app, player));
+
+    run_ticks(&mut app, 30);
+
+    let end = position(&app, zombie).distance(position(&app, player));
+    assert!(end < start - 20.0, "zombie went from {} to {} away from the player", start, end);
+}
+
+#[test]
+fn bullets_reduce_health() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let zombie = add_zombie(&mut app, Vec2::new(40.0, 0.0), 3);
+    let shooter = app.world.spawn_empty().id();
+    run_ticks(&mut app, 1);
+
+    with_commands(&mut app, |commands, world| {
+        spawn_bullet(commands, world.resource::<AssetServer>(), shooter, Vec3::ZERO, Vec2::new(40.0, 0.0))
+    });
+    run_ticks(&mut app, 20);
+
+    assert!(health(&app, zombie) < 3);
+}
+
+#[test]
+fn grenades_damage_in_radius() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let near = add_zombie(&mut app, Vec2::new(30.0, 0.0), 20);
+    let far = add_zombie(&mut app, Vec2::new(120.0, 0.0), 20);
+    app.world.spawn(GrenadeBundle {
+        sprite_bundle: SpriteBundle::default(),
+        collider_bundle: ColliderBundle {
+            collider: Collider::cuboid(0.5, 1.5),
+            rigid_body: RigidBody::Dynamic,
+            ..default()
+        },
+        timer: DetonationTimer {
+            detonation_timer: Timer::from_seconds(1.0, TimerMode::Once),
+        },
+        grenade: Grenade,
+    });
+
+    // A second at one tick per step, and some more
+    run_ticks(&mut app, (1.0 / FIXED_TIMESTEP) as u32 + 10);
+
+    assert_eq!(health(&app, near), 10);
+    assert_eq!(health(&app, far), 20);
+}
+
+#[test]
+fn kill_player_ends_the_game() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    app.world.resource_mut::<Lives>().remaining = 0;
+    let player = add_player(&mut app, Vec2::ZERO, InputDevice::KeyboardMouse);
+    app.world.get_mut::<Health>(player).unwrap().health_points = 0;
+
+    run_ticks(&mut app, 3);
+
+    assert_eq!(app_state(&app), AppState::GameOver);
+}
+
+#[test]
+fn kill_player_respawns_with_lives_left() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    app.world.resource_mut::<Lives>().remaining = 1;
+    let player = add_player(&mut app, Vec2::ZERO, InputDevice::KeyboardMouse);
+    app.world.get_mut::<Health>(player).unwrap().health_points = 0;
+
+    run_ticks(&mut app, 3);
+
+    assert_eq!(app_state(&app), AppState::Game);
+    assert!(app.world.get_entity(player).is_none());
+    assert_eq!(app.world.resource::<Lives>().remaining, 0);
+}
+
+#[test]
+fn bots_shoot_zombies_in_sight() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    add_player(&mut app, Vec2::ZERO, InputDevice::Bot);
+    let zombie = add_zombie(&mut app, Vec2::new(200.0, 0.0), 20);
+
+    // Time to reload the empty starting magazine and open fire
+    run_ticks(&mut app, 300);
+
+    assert!(health(&app, zombie) < 20);
+}
+
+#[test]
+fn snapshots_restore_gameplay_state() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let zombie = add_zombie(&mut app, Vec2::new(100.0, 0.0), 5);
+    run_ticks(&mut app, 2);
+    let snapshot = WorldSnapshot::capture(&mut app.world, 0);
+    let start = position(&app, zombie);
+
+    let bytes = snapshot.to_bytes().unwrap();
+    assert_eq!(WorldSnapshot::from_bytes(&bytes), Some(snapshot.clone()));
+
+    app.world.get_mut::<Health>(zombie).unwrap().health_points = 1;
+    app.world.get_mut::<Transform>(zombie).unwrap().translation = Vec3::new(300.0, 0.0, 0.0);
+    let shooter = app.world.spawn_empty().id();
+    let bullet = with_commands(&mut app, |commands, world| {
+        spawn_bullet(commands, world.resource::<AssetServer>(), shooter, Vec3::ZERO, Vec2::new(-40.0, 0.0))
+    });
+
+    assert_eq!(snapshot.restore(&mut app.world), 0);
+    assert_eq!(health(&app, zombie), 5);
+    assert_eq!(position(&app, zombie), start);
+    assert!(app.world.get_entity(bullet).is_none());
+}
+
+#[test]
+fn rewinding_goes_back_to_a_recorded_frame() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    add_player(&mut app, Vec2::ZERO, InputDevice::KeyboardMouse);
+    let zombie = add_zombie(&mut app, Vec2::new(100.0, 0.0), 5);
+    run_ticks(&mut app, 60);
+    let buffer = app.world.resource::<SnapshotBuffer>();
+    let latest = buffer.latest_tick().unwrap();
+    let recorded = buffer.get(latest - 30).unwrap();
+
+    assert_eq!(rewind(&mut app.world, 30), Some(latest - 30));
+
+    let id = app.world.get::<RollbackId>(zombie).unwrap().0;
+    let recorded_zombie = recorded.entities.iter().find(|snapshot| snapshot.id == id).unwrap();
+    assert_eq!(app.world.get::<Transform>(zombie).unwrap().translation, recorded_zombie.translation);
+    assert_eq!(app.world.resource::<SnapshotBuffer>().latest_tick(), Some(latest - 30));
+}
+
+#[test]
+fn restoring_brings_back_what_died_since() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let player = add_player(&mut app, Vec2::ZERO, InputDevice::KeyboardMouse);
+    let zombie = add_zombie(&mut app, Vec2::new(300.0, 0.0), 5);
+    app.world.insert_resource(RunStats::default());
+    app.world.get_mut::<MaxHealth>(player).unwrap().health_points = 15;
+    run_ticks(&mut app, 2);
+    let snapshot = WorldSnapshot::capture(&mut app.world, 0);
+    let zombie_id = *app.world.get::<RollbackId>(zombie).unwrap();
+    let lives = app.world.resource::<Lives>().remaining;
+
+    app.world.despawn(zombie);
+    app.world.resource_mut::<RunStats>().gun_kills += 1;
+    app.world.get_mut::<Health>(player).unwrap().health_points = 0;
+    run_ticks(&mut app, 1);
+    assert!(app.world.get_entity(player).is_none());
+
+    assert_eq!(snapshot.restore(&mut app.world), 0);
+    let zombies: Vec<(RollbackId, i32)> = app
+        .world
+        .query_filtered::<(&RollbackId, &Health), With<Zombie>>()
+        .iter(&app.world)
+        .map(|(id, health)| (*id, health.health_points))
+        .collect();
+    assert_eq!(zombies, vec![(zombie_id, 5)]);
+    let players: Vec<(PlayerId, i32)> = app
+        .world
+        .query::<(&PlayerId, &MaxHealth)>()
+        .iter(&app.world)
+        .map(|(player_id, max_health)| (*player_id, max_health.health_points))
+        .collect();
+    assert_eq!(players, vec![(PlayerId(0), 15)]);
+    assert_eq!(app.world.resource::<RunStats>().gun_kills, 0);
+    assert_eq!(app.world.resource::<Lives>().remaining, lives);
+    assert!(app.world.resource::<Respawns>().pending.is_empty());
+}
+
+#[test]
+fn saved_players_load_with_their_upgrades() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let player = add_player(&mut app, Vec2::ZERO, InputDevice::KeyboardMouse);
+    let upgrade = Pickup {
+        kind: PickupKind::Weapon,
+        amount: 50,
+        fire_rate: 0.05,
+        ..default()
+    };
+    let (mut health, max_health, mut weapon, mut inventory) = app
+        .world
+        .query::<(&mut Health, &MaxHealth, &mut Weapon, &mut Inventory)>()
+        .get_mut(&mut app.world, player)
+        .unwrap();
+    assert!(apply_pickup(&upgrade, &mut health, max_health, &mut weapon, &mut inventory));
+    health.health_points = 3;
+    let loadout = PlayerLoadout {
+        health: health.clone(),
+        max_health: max_health.clone(),
+        weapon: weapon.clone(),
+        inventory: inventory.clone(),
+    };
+
+    let saved = SavedPlayer::new(0, Vec2::new(12.0, 34.0), true, &loadout);
+    let text = ron::to_string(&saved).unwrap();
+    let loaded: SavedPlayer = ron::from_str(&text).unwrap();
+    assert_eq!(loaded, saved);
+
+    let restored = loaded.loadout();
+    assert_eq!(restored.health, loadout.health);
+    assert_eq!(restored.max_health, loadout.max_health);
+    assert_eq!(restored.weapon.ammo.bullets, 50);
+    assert_eq!(restored.weapon.mag_size, 50);
+    assert_eq!(restored.weapon.fire_rate.timer.duration(), loadout.weapon.fire_rate.timer.duration());
+    assert_eq!(
+        restored.weapon.reload_timer.reload_timer.duration(),
+        loadout.weapon.reload_timer.reload_timer.duration()
+    );
+    assert_eq!(restored.inventory.grenades, loadout.inventory.grenades);
+}
+
+#[test]
+fn edited_saves_keep_weapon_timers_running() {
+    let saved = SavedPlayer {
+        slot: 0,
+        position: Vec2::ZERO,
+        facing_left: false,
+        health: 10,
+        max_health: 10,
+        bullets: 30,
+        mag_size: 30,
+        fire_rate: -1.0,
+        reload_time: 0.0,
+        grenades: 3,
+        keys: Vec::new(),
+    };
+
+    let restored = saved.loadout();
+    assert!(restored.weapon.fire_rate.timer.duration() > Duration::ZERO);
+    assert!(restored.weapon.reload_timer.reload_timer.duration() > Duration::ZERO);
+}
+
+fn objectives_done(app: &App) -> Vec<bool> {
+    app.world
+        .resource::<MissionTracker>()
+        .objectives()
+        .iter()
+        .map(|objective| objective.completed())
+        .collect()
+}
+
+#[test]
+fn objectives_complete_one_after_another() {
+    let mut app = headless_app();
+    app
+        .add_event::<LevelEntered>()
+        .add_event::<LevelExited>()
+        .add_event::<SurvivorRescued>()
+        .add_event::<PickupCollected>()
+        .add_plugin(ObjectivePlugin);
+    start_game(&mut app);
+    let mut tracker = app.world.resource_mut::<MissionTracker>();
+    tracker.levels.insert("headless".to_string(), vec![
+        Objective::new(ObjectiveKind::KillZombies, 1.0),
+        Objective::new(ObjectiveKind::ReachExit, 1.0),
+    ]);
+    tracker.current = Some("headless".to_string());
+    let player = add_player(&mut app, Vec2::ZERO, InputDevice::KeyboardMouse);
+    app.world.spawn((
+        SpatialBundle::from_transform(Transform::from_xyz(400.0, 0.0, 0.0)),
+        LevelExit {
+            size: Vec2::splat(32.0),
+            ..default()
+        },
+    ));
+    run_ticks(&mut app, 5);
+    assert_eq!(objectives_done(&app), vec![false, false]);
+
+    add_zombie(&mut app, Vec2::new(-400.0, 0.0), 0);
+    run_ticks(&mut app, 5);
+    assert_eq!(objectives_done(&app), vec![true, false]);
+
+    app.world.get_mut::<Transform>(player).unwrap().translation = Vec3::new(400.0, 0.0, 0.0);
+    run_ticks(&mut app, 5);
+    assert_eq!(objectives_done(&app), vec![true, true]);
+}
+
+#[test]
+fn big_snapshots_are_split_to_fit_in_a_message() {
+    let entities: Vec<NetEntity> = (0..200)
+        .map(|id| NetEntity {
+            id: NetId(id),
+            kind: NetKind::Zombie,
+            position: Vec2::new(id as f32, 0.0),
+            velocity: Vec2::ZERO,
+            health: Some(2),
+            weapon: None,
+        })
+        .collect();
+
+    let parts = split_snapshot(9, HostState::Playing, 0, entities.clone());
+
+    assert!(parts.len() > 1);
+    for (index, part) in parts.iter().enumerate() {
+        assert_eq!((part.tick, part.part, part.parts), (9, index as u32, parts.len() as u32));
+        let message = ServerMessage::Snapshot(part.clone());
+        assert!(bincode::serialized_size(&message).unwrap() as usize <= MAX_MESSAGE);
+    }
+    let rejoined: Vec<NetEntity> = parts.into_iter().flat_map(|part| part.entities).collect();
+    assert_eq!(rejoined, entities);
+}
+
+#[test]
+fn net_messages_survive_encoding() {
+    let input = ClientMessage::Input {
+        ack: 42,
+        actions: PlayerActions {
+            movement: Vec2::new(1.0, -1.0),
+            aim: Vec2::new(120.0, 80.0),
+            fire: true,
+            ..default()
+        },
+    };
+    let snapshot = Snapshot {
+        tick: 7,
+        part: 0,
+        parts: 1,
+        state: HostState::Playing,
+        world: 1,
+        entities: vec![NetEntity {
+            id: NetId(3),
+            kind: NetKind::Player { slot: 1 },
+            position: Vec2::new(10.0, 20.0),
+            velocity: Vec2::new(-1.0, 0.5),
+            health: Some(4),
+            weapon: Some(NetWeapon {
+                bullets: 9,
+                mag_size: 30,
+                reloading: false,
+            }),
+        }],
+    };
+
+    for message in [input, ClientMessage::Hello { version: NET_VERSION }, ClientMessage::Bye] {
+        let bytes = bincode::serialize(&message).unwrap();
+        assert_eq!(bincode::deserialize::<ClientMessage>(&bytes).unwrap(), message);
+    }
+    let bytes = bincode::serialize(&snapshot).unwrap();
+    assert_eq!(bincode::deserialize::<Snapshot>(&bytes).unwrap(), snapshot);
+    let replies = [
+        ServerMessage::Welcome { slot: 2 },
+        ServerMessage::Refused { reason: "the game is full".to_string() },
+        ServerMessage::Snapshot(snapshot),
+    ];
+    for message in replies {
+        let bytes = bincode::serialize(&message).unwrap();
+        assert_eq!(bincode::deserialize::<ServerMessage>(&bytes).unwrap(), message);
+    }
+}
+
+/// Says hello to the host on localhost from a new client, and waits a little for the answer
+fn join(app: &mut App, host: SocketAddr, version: u32) -> Option<ServerMessage> {
+    let client = NetSocket::bind(0).unwrap();
+    client.send(host, &ClientMessage::Hello { version });
+    for _ in 0..100 {
+        app.update();
+        if let Some((_, reply)) = client.receive::<ServerMessage>().pop() {
+            return Some(reply);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    None
+}
+
+#[test]
+fn hosts_welcome_clients_of_their_version() {
+    let mut app = headless_app();
+    let socket = NetSocket::bind(0).unwrap();
+    let host = SocketAddr::from((Ipv4Addr::LOCALHOST, socket.0.local_addr().unwrap().port()));
+    app
+        .insert_resource(socket)
+        .init_resource::<Connections>()
+        .add_system(receive_client_messages);
+
+    assert_eq!(join(&mut app, host, NET_VERSION), Some(ServerMessage::Welcome { slot: 1 }));
+    assert_eq!(join(&mut app, host, NET_VERSION), Some(ServerMessage::Welcome { slot: 2 }));
+    assert!(matches!(join(&mut app, host, NET_VERSION + 1), Some(ServerMessage::Refused { .. })));
+    assert_eq!(app.world.resource::<Connections>().clients.len(), 2);
+}
+
+/// A bot fighting a few zombies for `ticks` steps, recorded, or played back from `replay`.
+/// Returns the recording and where the player and zombies end up with how much health.
+fn replay_run(mode: ReplayMode, replay: Replay, ticks: u32) -> (Replay, Vec<Option<(Vec2, i32)>>) {
+    let mut app = headless_app();
+    app
+        .insert_resource(mode)
+        .insert_resource(replay)
+        .init_resource::<ReplayCursor>()
+        .add_systems(
+            (
+                record_frame.run_if(is_recording).after(read_player_input),
+                play_frame.run_if(is_playing_back).before(read_player_input),
+            )
+            .in_set(OnUpdate(AppState::Game))
+            .in_set(OnUpdate(SimulationState::Running))
+        );
+    start_game(&mut app);
+    let mut bodies = vec![add_player(&mut app, Vec2::ZERO, InputDevice::Bot)];
+    for position in [Vec2::new(120.0, 0.0), Vec2::new(-100.0, 60.0), Vec2::new(40.0, -140.0)] {
+        bodies.push(add_zombie(&mut app, position, 5));
+    }
+
+    run_ticks(&mut app, ticks);
+
+    let end = bodies
+        .into_iter()
+        .map(|body| app.world.get_entity(body).map(|_| (position(&app, body), health(&app, body))))
+        .collect();
+    (app.world.resource::<Replay>().clone(), end)
+}
+
+#[test]
+fn replays_play_back_the_same_run() {
+    let (recorded, recorded_end) = replay_run(ReplayMode::Record(PathBuf::new()), Replay::default(), 240);
+    assert_eq!(recorded.frames.len(), 240);
+
+    let ticks = recorded.frames.len() as u32;
+    let (_, played_end) = replay_run(ReplayMode::Playback(PathBuf::new()), recorded, ticks);
+
+    assert_eq!(played_end, recorded_end);
+}
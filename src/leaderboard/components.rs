@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::stats::components::RunStats;
+use crate::storage::Timestamp;
+
+/// Runs kept on the leaderboard, the lowest score drops off
+pub const MAX_HIGH_SCORES: usize = 10;
+
+/// A finished run as it is kept on the leaderboard
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: u32,
+    pub kills: u32,
+    /// In percent
+    pub accuracy: f32,
+    pub time_survived: f32,
+    pub levels_reached: u32,
+    pub recorded_at: Timestamp,
+}
+
+impl HighScore {
+    pub fn new(stats: &RunStats, recorded_at: Timestamp) -> Self {
+        Self {
+            score: stats.score(),
+            kills: stats.kills(),
+            accuracy: stats.accuracy(),
+            time_survived: stats.time_survived,
+            levels_reached: stats.levels_reached,
+            recorded_at,
+        }
+    }
+}
+
+/// Best runs on this machine, highest score first
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// Adds `entry` in its place, returns its rank from 1 if it made the table
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        let index = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+        if index >= MAX_HIGH_SCORES {
+            return None;
+        }
+        self.entries.insert(index, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(index + 1)
+    }
+}
+
+/// Overlay of the main menu showing the high scores
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum LeaderboardState {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(Component)]
+pub struct Leaderboard {}
+
+#[derive(Component)]
+pub struct LeaderboardBackButton {}
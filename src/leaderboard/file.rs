@@ -0,0 +1,37 @@
+use std::fs;
+
+use ron::ser::PrettyConfig;
+
+use crate::storage::data_path;
+use super::components::HighScores;
+
+pub const HIGH_SCORES_FILE: &str = "highscores.ron";
+
+impl HighScores {
+    /// High scores saved so far, or an empty table if there are none yet
+    pub fn load() -> HighScores {
+        let Ok(text) = fs::read_to_string(data_path(HIGH_SCORES_FILE)) else {
+            return HighScores::default();
+        };
+        match ron::from_str(&text) {
+            Ok(high_scores) => high_scores,
+            Err(error) => {
+                println!("Could not read {}: {}", HIGH_SCORES_FILE, error);
+                HighScores::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let text = match ron::ser::to_string_pretty(self, PrettyConfig::default()) {
+            Ok(text) => text,
+            Err(error) => {
+                println!("Could not write high scores: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = fs::write(data_path(HIGH_SCORES_FILE), text) {
+            println!("Could not write {}: {}", HIGH_SCORES_FILE, error);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+pub mod components;
+pub mod file;
+mod systems;
+
+use crate::{AppState, HostGameplaySet};
+
+use self::components::{HighScores, LeaderboardState};
+use self::systems::{
+    layout::{spawn_leaderboard, despawn_leaderboard},
+    interactions::{interact_with_leaderboard_back_button, escape_leaderboard, close_leaderboard},
+    records::record_high_score,
+};
+
+/// High score table kept next to the executable, shown from the main menu
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_state::<LeaderboardState>()
+        .insert_resource(HighScores::load())
+        .add_system(record_high_score.in_set(HostGameplaySet).in_schedule(OnEnter(AppState::GameOver)))
+        //OnEnter State Systems
+        .add_system(spawn_leaderboard.in_schedule(OnEnter(LeaderboardState::Open)))
+        //Systems
+        .add_systems(
+            (
+                interact_with_leaderboard_back_button,
+                escape_leaderboard,
+            ).in_set(OnUpdate(LeaderboardState::Open))
+        )
+        .add_system(close_leaderboard.in_schedule(OnExit(AppState::MainMenu)))
+        //OnExit State Systems
+        .add_system(despawn_leaderboard.in_schedule(OnExit(LeaderboardState::Open)));
+    }
+}
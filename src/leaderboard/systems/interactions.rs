@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::leaderboard::components::*;
+use crate::main_menu::styles::*;
+use crate::systems::ButtonInteraction;
+
+pub fn interact_with_leaderboard_back_button(
+    mut button_query: ButtonInteraction<LeaderboardBackButton>,
+    mut leaderboard_state_next_state: ResMut<NextState<LeaderboardState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                leaderboard_state_next_state.set(LeaderboardState::Closed);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn escape_leaderboard(
+    keyboard: Res<Input<KeyCode>>,
+    mut leaderboard_state_next_state: ResMut<NextState<LeaderboardState>>
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        leaderboard_state_next_state.set(LeaderboardState::Closed);
+    }
+}
+
+/// The main menu went away
+pub fn close_leaderboard(
+    mut leaderboard_state_next_state: ResMut<NextState<LeaderboardState>>
+) {
+    leaderboard_state_next_state.set(LeaderboardState::Closed);
+}
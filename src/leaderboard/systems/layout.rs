@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::leaderboard::components::*;
+use crate::main_menu::styles::*;
+
+pub fn spawn_leaderboard(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+) {
+    build_leaderboard(&mut commands, &asset_server, &high_scores);
+}
+
+pub fn despawn_leaderboard(
+    mut commands: Commands,
+    leaderboard_query: Query<Entity, With<Leaderboard>>
+) {
+    if let Ok(leaderboard_entity) = leaderboard_query.get_single() {
+        commands.entity(leaderboard_entity).despawn_recursive();
+    }
+}
+
+fn high_score_line(rank: usize, entry: &HighScore) -> String {
+    let seconds = entry.time_survived as u32;
+    format!(
+        "{}. {}   Kills {}   Accuracy {:.0}%   Time {}:{:02}   Levels {}",
+        rank,
+        entry.score,
+        entry.kills,
+        entry.accuracy,
+        seconds / 60,
+        seconds % 60,
+        entry.levels_reached,
+    )
+}
+
+pub fn build_leaderboard(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    high_scores: &HighScores,
+) -> Entity {
+    let leaderboard_entity = commands.spawn(
+        (
+            NodeBundle {
+                style: SETTINGS_MENU_STYLE,
+                background_color: SETTINGS_BACKGROUND_COLOR.into(),
+                // Drawn over the main menu and keeps clicks away from it
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            Leaderboard{},
+        )
+    )
+    .with_children(|parent| {
+        //title
+        parent.spawn(
+            NodeBundle {
+                style: TITLE_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            parent.spawn(
+                TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Leaderboard",
+                                get_title_text_style(asset_server)
+                            )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //high scores
+        parent.spawn(
+            NodeBundle {
+                style: SETTINGS_COLUMN_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            if high_scores.entries.is_empty() {
+                parent.spawn(
+                    TextBundle::from_section(
+                        "No runs finished yet",
+                        get_setting_text_style(asset_server),
+                    )
+                );
+            }
+            for (index, entry) in high_scores.entries.iter().enumerate() {
+                parent.spawn(
+                    TextBundle::from_section(
+                        high_score_line(index + 1, entry),
+                        get_setting_text_style(asset_server),
+                    )
+                );
+            }
+        });
+        //back button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: NORMAL_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                LeaderboardBackButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Back",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+    })
+    .id();
+
+    leaderboard_entity
+}
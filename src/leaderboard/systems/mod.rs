@@ -0,0 +1,3 @@
+pub mod interactions;
+pub mod layout;
+pub mod records;
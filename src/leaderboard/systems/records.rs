@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::game::stats::components::RunStats;
+use crate::leaderboard::components::{HighScore, HighScores};
+use crate::storage::now;
+
+/// Every run that ends on the Game Over screen gets a shot at the table
+pub fn record_high_score(
+    stats: Res<RunStats>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    match high_scores.insert(HighScore::new(&stats, now())) {
+        Some(rank) => {
+            println!("New high score, rank {}", rank);
+            high_scores.save();
+        }
+        None => println!("Score of {} did not make the leaderboard", stats.score()),
+    }
+}
@@ -1,35 +1,82 @@
-use bevy::{prelude::*};
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
 use bevy_ecs_ldtk::prelude::*;
-use bevy_rapier2d::prelude::*;
 
 mod resources;
 mod components;
+mod events;
 mod systems;
 mod graphics;
+mod fields;
 mod game;
 mod main_menu;
+mod game_over;
+mod pause_menu;
+mod settings;
+mod sound;
+mod save;
+mod leaderboard;
+mod storage;
+mod replay;
+mod net;
+mod rollback;
+mod simulation;
+mod headless;
 
+use game::bot::BotPlugin;
+use game::buddy::BuddyPlugin;
+use game::campaign::CampaignPlugin;
+use game::checkpoints::CheckpointPlugin;
 use game::enemies::EnemyPlugin;
+use game::pickups::PickupPlugin;
+use game::doors::DoorPlugin;
+use game::interaction::InteractionPlugin;
+use game::npcs::NpcPlugin;
+use game::objectives::ObjectivePlugin;
 use game::player::PlayerPlugin;
+use game::rescue::RescuePlugin;
+use game::stats::StatsPlugin;
 use game::ui::GameUIPlugin;
 use main_menu::MainMenuPlugin;
-use resources::MouseLoc;
+use game_over::GameOverPlugin;
+use pause_menu::PauseMenuPlugin;
+use settings::SettingsPlugin;
+use settings::components::Settings;
+use sound::SoundPlugin;
+use save::SavePlugin;
+use leaderboard::LeaderboardPlugin;
+use replay::ReplayPlugin;
+use net::NetPlugin;
+use rollback::RollbackPlugin;
+use simulation::SimulationPlugin;
+use events::{LevelEntered, LevelExited};
+use resources::{LoadedLevels, MouseLoc};
 use graphics::GraphicsPlugin;
 
 use systems::*;
 
 fn main() {
+    if let Some(ticks) = headless::ticks_from_args() {
+        if !headless::soak::run(ticks) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let settings = Settings::load();
     App::new()
-        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugins(
+            DefaultPlugins
+                .set(ImagePlugin::default_nearest())
+                .set(WindowPlugin {
+                    primary_window: Some(settings.primary_window()),
+                    ..default()
+                })
+        )
+        .insert_resource(settings)
         .add_plugin(LdtkPlugin)
-        .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
+        .add_plugin(SimulationPlugin)
         .add_plugin(GraphicsPlugin)
-        // Required to prevent race conditions between bevy_ecs_ldtk's and bevy_rapier's systems
-        .configure_set(LdtkSystemSet::ProcessApi.before(PhysicsSet::SyncBackend))
-        .insert_resource(RapierConfiguration {
-            gravity: Vec2::new(0.0, 0.0),
-            ..Default::default()
-        })
         .insert_resource(LevelSelection::Uid(0))
         .insert_resource(LdtkSettings {
             level_spawn_behavior: LevelSpawnBehavior::UseWorldTranslation { 
@@ -39,22 +86,52 @@ fn main() {
             ..Default::default()
         }) 
         .insert_resource(MouseLoc{ loc: Vec2::ZERO})
+        .init_resource::<LoadedLevels>()
+        .add_event::<LevelEntered>()
+        .add_event::<LevelExited>()
         .insert_resource(Edges{edges: vec![Edge {sx: 0.0, sy: 0.0, ex: 0.0, ey: 0.0}]})
         .add_state::<AppState>()
+        .add_state::<SimulationState>()
         .add_plugin(MainMenuPlugin)
+        .add_plugin(GameOverPlugin)
+        .add_plugin(PauseMenuPlugin)
+        .add_plugin(SettingsPlugin)
+        .add_plugin(SoundPlugin)
         .add_plugin(GameUIPlugin)
         .add_plugin(EnemyPlugin)
         .add_plugin(PlayerPlugin)
+        .add_plugin(BuddyPlugin)
+        .add_plugin(BotPlugin)
+        .add_plugin(PickupPlugin)
+        .add_plugin(DoorPlugin)
+        .add_plugin(InteractionPlugin)
+        .add_plugin(NpcPlugin)
+        .add_plugin(RescuePlugin)
+        .add_plugin(ObjectivePlugin)
+        .add_plugin(CampaignPlugin)
+        .add_plugin(CheckpointPlugin)
+        .add_plugin(StatsPlugin)
+        .add_plugin(SavePlugin)
+        .add_plugin(LeaderboardPlugin)
+        .add_plugin(ReplayPlugin)
+        .add_plugin(NetPlugin)
+        .add_plugin(RollbackPlugin)
         .add_startup_system(setup)
-        .add_system(update_level_selection)
-        .add_system(bullet_collisions)
-        .add_system(spawn_buddy)
+        .add_system(despawn_state_scoped(AppState::MainMenu).in_schedule(OnExit(AppState::MainMenu)))
+        .add_system(despawn_state_scoped(AppState::Loading).in_schedule(OnExit(AppState::Loading)))
+        .add_system(despawn_state_scoped(AppState::Game).in_schedule(OnExit(AppState::Game)))
+        .add_system(despawn_state_scoped(AppState::GameOver).in_schedule(OnExit(AppState::GameOver)))
+        .add_system(update_loaded_levels.before(despawn_stray_bullets))
+        .add_system(update_level_selection.after(update_loaded_levels))
         .add_system(mouse_movement_updating_system)
         .add_system(spawn_wall_collision)
-        .add_system(blow_up_granade)
-        .add_system(camera_fit_inside_current_level)
+        .add_system(
+            camera_fit_inside_current_level
+                .after(interpolate_transforms)
+                .in_base_set(CoreSet::PostUpdate)
+                .before(TransformSystem::TransformPropagate)
+        )
         .register_ldtk_int_cell::<components::WallBundle>(1)
-        .register_ldtk_int_cell::<components::SpawnBundle>(2)
         .register_ldtk_entity::<components::ZombieBundle>("Zombie")
         .run();
 }
@@ -63,6 +140,29 @@ fn main() {
 pub enum AppState {
     #[default]
     MainMenu, 
+    /// Loading screen while a campaign world is spawned
+    Loading,
     Game, 
     GameOver,
-}
\ No newline at end of file
+}
+
+/// Seconds one step of `CoreSchedule::FixedUpdate` simulates, whatever the frame rate
+pub const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
+
+/// Gameplay that steps together with physics in `CoreSchedule::FixedUpdate`, right after rapier's writeback.
+/// Only runs while in `AppState::Game` and `SimulationState::Running`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedGameplaySet;
+
+/// Gameplay outside the fixed steps that decides how the game goes. Clients of a co-op game
+/// leave it to the host and only get the outcome.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostGameplaySet;
+
+/// Whether gameplay runs, only ever `Paused` while in `AppState::Game`
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum SimulationState {
+    #[default]
+    Running,
+    Paused,
+}
@@ -7,4 +7,16 @@ pub struct MainMenu {}
 pub struct PlayButton {}
 
 #[derive(Component)]
-pub struct QuitButton {}
\ No newline at end of file
+pub struct ContinueButton {}
+
+#[derive(Component)]
+pub struct LoadGameButton {}
+
+#[derive(Component)]
+pub struct LeaderboardButton {}
+
+#[derive(Component)]
+pub struct QuitButton {}
+
+#[derive(Component)]
+pub struct SettingsButton {}
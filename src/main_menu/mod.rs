@@ -1,12 +1,12 @@
 use bevy::prelude::*;
 
 mod components;
-mod styles;
+pub(crate) mod styles;
 mod systems;
 
 use crate::AppState;
 
-use self::systems::{layout::{spawn_main_menu, despawn_main_menu}, interactions::{interact_with_play_button, interact_with_quit_button}};
+use self::systems::{layout::{spawn_main_menu, despawn_main_menu}, interactions::{interact_with_play_button, interact_with_continue_button, interact_with_load_game_button, interact_with_settings_button, interact_with_leaderboard_button, interact_with_quit_button}};
 
 pub struct MainMenuPlugin;
 
@@ -19,6 +19,10 @@ impl Plugin for MainMenuPlugin {
         .add_systems(
             (
                 interact_with_play_button,
+                interact_with_continue_button,
+                interact_with_load_game_button,
+                interact_with_settings_button,
+                interact_with_leaderboard_button,
                 interact_with_quit_button
             ).in_set(OnUpdate(AppState::MainMenu))
         )
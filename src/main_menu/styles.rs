@@ -20,6 +20,15 @@ pub const NORMAL_BUTTON_STYLE: Style = Style {
     ..Style::DEFAULT
 };
 
+/// Shorter than the normal buttons so every main menu entry fits a 720p window
+pub const MAIN_MENU_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(200.0),Val::Px(64.0)),
+    ..Style::DEFAULT
+};
+
+#[allow(dead_code)]
 pub const IMAGE_STYLE: Style = Style {
     size: Size::new(Val::Px(64.0),Val::Px(64.0)),
     margin: UiRect::new(
@@ -38,6 +47,37 @@ pub const TITLE_STYLE: Style = Style {
     ..Style::DEFAULT
 };
 
+pub const SETTINGS_MENU_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Percent(100.0),Val::Percent(100.0)),
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
+pub const SETTINGS_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+
+pub const SETTINGS_COLUMNS_STYLE: Style = Style {
+    flex_direction: FlexDirection::Row,
+    gap: Size::new(Val::Px(24.0), Val::Px(0.0)),
+    ..Style::DEFAULT
+};
+
+pub const SETTINGS_COLUMN_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    gap: Size::new(Val::Px(0.0), Val::Px(6.0)),
+    ..Style::DEFAULT
+};
+
+pub const SETTING_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(320.0),Val::Px(40.0)),
+    ..Style::DEFAULT
+};
+
 pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     TextStyle {
         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
@@ -54,3 +94,10 @@ pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     }
 }
 
+pub fn get_setting_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 22.0,
+        color: Color::WHITE,
+    }
+}
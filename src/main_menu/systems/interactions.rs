@@ -4,19 +4,108 @@ use bevy::prelude::*;
 use crate::AppState;
 use crate::main_menu::components::*;
 use crate::main_menu::styles::*;
+use crate::leaderboard::components::LeaderboardState;
+use crate::save::components::{LoadMenuState, PendingLoad, SaveGame};
+use crate::settings::components::SettingsState;
+use crate::systems::ButtonInteraction;
 
 pub fn interact_with_play_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<PlayButton>)
-    >,
+    mut button_query: ButtonInteraction<PlayButton>,
     mut app_state_next_state: ResMut<NextState<AppState>>
 ) {
     if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
         match *interaction {
             Interaction::Clicked => {
                 *background_color = PRESSED_BUTTON_COLOR.into();
-                app_state_next_state.set(AppState::Game);
+                app_state_next_state.set(AppState::Loading);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+/// Picks up the most recent save of any slot
+pub fn interact_with_continue_button(
+    mut button_query: ButtonInteraction<ContinueButton>,
+    mut pending_load: ResMut<PendingLoad>,
+    mut app_state_next_state: ResMut<NextState<AppState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                match SaveGame::newest() {
+                    Some(save) => {
+                        pending_load.0 = Some(save);
+                        app_state_next_state.set(AppState::Loading);
+                    }
+                    None => println!("There is no saved game to continue"),
+                }
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_load_game_button(
+    mut button_query: ButtonInteraction<LoadGameButton>,
+    mut load_menu_state_next_state: ResMut<NextState<LoadMenuState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                load_menu_state_next_state.set(LoadMenuState::Open);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_settings_button(
+    mut button_query: ButtonInteraction<SettingsButton>,
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                settings_state_next_state.set(SettingsState::Open);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_leaderboard_button(
+    mut button_query: ButtonInteraction<LeaderboardButton>,
+    mut leaderboard_state_next_state: ResMut<NextState<LeaderboardState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                leaderboard_state_next_state.set(LeaderboardState::Open);
             }
             Interaction::Hovered => {
                 *background_color = HOVERED_BUTTON_COLOR.into();
@@ -30,9 +119,7 @@ pub fn interact_with_play_button(
 
 pub fn interact_with_quit_button(
     mut app_exit_event_writer: EventWriter<AppExit>,
-    mut button_query: Query<
-    (&Interaction, &mut BackgroundColor),
-    (Changed<Interaction>, With<QuitButton>)>
+    mut button_query: ButtonInteraction<QuitButton>
 ) {
     if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
         match *interaction {
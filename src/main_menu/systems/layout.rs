@@ -7,7 +7,7 @@ pub fn spawn_main_menu(
     mut commands: Commands,
     asset_server: Res<AssetServer>
 ) {
-    let main_menu_entity = build_main_menu(&mut commands, &asset_server);
+    build_main_menu(&mut commands, &asset_server);
 }
 
 pub fn despawn_main_menu(
@@ -62,7 +62,7 @@ pub fn build_main_menu(
         parent.spawn(
             (
                 ButtonBundle {
-                    style: NORMAL_BUTTON_STYLE,
+                    style: MAIN_MENU_BUTTON_STYLE,
                     background_color: NORMAL_BUTTON_COLOR.into(),
                     ..default()
                 },
@@ -86,11 +86,123 @@ pub fn build_main_menu(
                 }
             );
         });
+        //continue button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: MAIN_MENU_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                ContinueButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Continue",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,   
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //load game button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: MAIN_MENU_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                LoadGameButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Load Game",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,   
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //settings button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: MAIN_MENU_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                SettingsButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Settings",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,   
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //leaderboard button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: MAIN_MENU_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                LeaderboardButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Leaderboard",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,   
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
         //quit button
         parent.spawn(
             (
                 ButtonBundle {
-                    style: NORMAL_BUTTON_STYLE,
+                    style: MAIN_MENU_BUTTON_STYLE,
                     background_color: NORMAL_BUTTON_COLOR.into(),
                     ..default()
                 },
@@ -104,7 +216,7 @@ pub fn build_main_menu(
                         sections: vec![
                             TextSection::new(
                                 "Quit",
-                                get_button_text_style(&asset_server)
+                                get_button_text_style(asset_server)
                                 )
                         ],
                         alignment: TextAlignment::Center,   
@@ -125,7 +237,7 @@ pub fn build_main_menu(
                         TextSection::new(
                             "Find Oscilloscope to win.",
                             TextStyle {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf").into(),
+                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 32.0,
                                 color: Color::WHITE,
                             }
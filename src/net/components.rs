@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+use std::net::{SocketAddr, UdpSocket};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::player::components::PlayerActions;
+
+/// Bumped whenever the messages change shape, hosts and clients of other versions don't talk
+pub const NET_VERSION: u32 = 2;
+
+pub const DEFAULT_PORT: u16 = 7777;
+
+/// Biggest message sent. Datagrams over the ~1500 byte MTU of most links are fragmented,
+/// and losing any one fragment loses all of it.
+pub const MAX_MESSAGE: usize = 1200;
+
+/// Picked on the command line with `--host [port]` or `--join <address>`
+#[derive(Resource, Clone, PartialEq, Debug, Default)]
+pub enum NetMode {
+    #[default]
+    Off,
+    /// Runs the game for everyone and takes players in on this port
+    Host(u16),
+    /// Plays in the game of the host at this address
+    Client(SocketAddr),
+}
+
+/// Sent by clients to the host
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Asks to join, repeated until the host answers
+    Hello { version: u32 },
+    /// What the client's player wants to do. `ack` is the newest snapshot the client has,
+    /// which tells the host how far behind the client sees the game.
+    Input { ack: u32, actions: PlayerActions },
+    Bye,
+}
+
+/// Sent by the host to clients
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// The client plays as player `slot`
+    Welcome { slot: usize },
+    /// No free slot or a different version
+    Refused { reason: String },
+    Snapshot(Snapshot),
+}
+
+/// Where the host's game is at
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum HostState {
+    Menu,
+    Playing,
+    GameOver,
+}
+
+/// Everything replicated, as the host has it after fixed step `tick`.
+/// Sent in `parts` that each fit in a message, `part` counts from 0.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub tick: u32,
+    pub part: u32,
+    pub parts: u32,
+    pub state: HostState,
+    /// Campaign world the host plays in, index into the manifest
+    pub world: usize,
+    pub entities: Vec<NetEntity>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum NetKind {
+    Player { slot: usize },
+    Zombie,
+    Bullet,
+    Grenade,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct NetWeapon {
+    pub bullets: u32,
+    pub mag_size: u32,
+    pub reloading: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct NetEntity {
+    pub id: NetId,
+    pub kind: NetKind,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub health: Option<i32>,
+    pub weapon: Option<NetWeapon>,
+}
+
+/// Same on the host and every client for one replicated entity
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Component, Serialize, Deserialize)]
+pub struct NetId(pub u64);
+
+/// Non-blocking socket of the host or client
+#[derive(Resource, Debug)]
+pub struct NetSocket(pub UdpSocket);
+
+#[derive(Clone, Debug)]
+pub struct Connection {
+    pub slot: usize,
+    /// Seconds since the client was last heard of
+    pub silence: f32,
+    /// Newest snapshot the client has
+    pub ack: u32,
+    /// The player still has to be spawned, after joining or once a new map is in
+    pub needs_spawn: bool,
+}
+
+/// Clients of the host, by address
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Connections {
+    pub clients: HashMap<SocketAddr, Connection>,
+}
+
+/// Host side count of fixed steps and replicated entities
+#[derive(Resource, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct HostTick {
+    pub tick: u32,
+    pub next_id: u64,
+}
+
+/// The client's view of the host
+#[derive(Resource, Clone, Debug)]
+pub struct ClientSession {
+    pub host: SocketAddr,
+    /// Player slot the host gave us, `None` until welcomed
+    pub slot: Option<usize>,
+    /// Newest snapshot applied, older ones arriving late are dropped
+    pub tick: u32,
+    pub state: HostState,
+    pub world: usize,
+    pub hello_timer: Timer,
+    /// Seconds since the host was last heard of
+    pub silence: f32,
+    /// Replicated entities by their id on the host
+    pub entities: HashMap<NetId, Entity>,
+    pub parts: SnapshotParts,
+}
+
+/// Parts of the newest snapshot received so far. Entities it leaves out only go away
+/// once all of its parts are in.
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotParts {
+    pub tick: u32,
+    pub received: HashSet<u32>,
+    pub seen: HashSet<NetId>,
+}
+
+/// Moves a replicated entity from where it was drawn to where the newest snapshot has it,
+/// over the time until the next snapshot
+#[derive(Copy, Clone, PartialEq, Debug, Component)]
+pub struct NetInterpolation {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub elapsed: f32,
+}
+
+impl ClientSession {
+    pub fn new(host: SocketAddr) -> Self {
+        Self {
+            host,
+            slot: None,
+            tick: 0,
+            state: HostState::Menu,
+            world: 0,
+            hello_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            silence: 0.0,
+            entities: HashMap::new(),
+            parts: SnapshotParts::default(),
+        }
+    }
+}
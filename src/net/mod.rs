@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+pub mod components;
+pub mod socket;
+pub(crate) mod systems;
+
+use crate::{AppState, FixedGameplaySet, HostGameplaySet, SimulationState};
+use crate::game::player::input::read_player_input;
+
+use self::components::{ClientSession, Connections, HostTick, NetMode, NetSocket};
+use self::systems::client::*;
+use self::systems::host::*;
+use self::systems::not_client;
+
+/// Co-op over UDP. The host runs the game as usual and takes remote players in, clients send
+/// their input and draw the players, zombies, bullets and grenades the host replicates.
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        let mode = NetMode::from_args();
+        let socket = match &mode {
+            NetMode::Off => None,
+            NetMode::Host(port) => NetSocket::bind(*port),
+            // Any free port, the host answers to whichever it is
+            NetMode::Client(_) => NetSocket::bind(0),
+        };
+        // Without a socket the game is played alone
+        let Some(socket) = socket else {
+            app.init_resource::<NetMode>();
+            return;
+        };
+        app.insert_resource(socket).insert_resource(mode.clone());
+
+        match mode {
+            NetMode::Host(port) => {
+                println!("Hosting on port {}", port);
+                app
+                .init_resource::<Connections>()
+                .init_resource::<HostTick>()
+                .add_system(receive_client_messages)
+                .add_system(assign_net_ids)
+                .add_system(respawn_remote_players_on_load.in_schedule(OnEnter(AppState::Game)))
+                .add_systems(
+                    (
+                        spawn_remote_players,
+                        fast_forward_remote_bullets,
+                    )
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(SimulationState::Running))
+                )
+                .add_system(
+                    send_snapshots
+                        .after(FixedGameplaySet)
+                        .in_schedule(CoreSchedule::FixedUpdate)
+                );
+            }
+            NetMode::Client(host) => {
+                app
+                .insert_resource(ClientSession::new(host))
+                .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                    schedule.configure_set(FixedGameplaySet.run_if(not_client));
+                })
+                .configure_set(HostGameplaySet.run_if(not_client))
+                .edit_schedule(OnEnter(AppState::GameOver), |schedule| {
+                    schedule.configure_set(HostGameplaySet.run_if(not_client));
+                })
+                .add_system(say_hello)
+                .add_system(receive_host_messages.after(say_hello))
+                .add_system(follow_host.after(receive_host_messages))
+                .add_system(despawn_unreplicated)
+                .add_system(send_input.after(read_player_input))
+                .add_system(enter_host_world.in_schedule(OnEnter(AppState::Loading)))
+                .add_systems(
+                    (
+                        predict_own_player
+                            .after(read_player_input)
+                            .before(send_input),
+                        interpolate_replicated,
+                    )
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(SimulationState::Running))
+                )
+                .add_system(say_goodbye.in_base_set(CoreSet::Last));
+            }
+            NetMode::Off => {}
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::env;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::components::{NetMode, NetSocket, DEFAULT_PORT, MAX_MESSAGE};
+
+/// Biggest datagram read, bigger than any sent so an oversized one still gets through
+const MAX_DATAGRAM: usize = 64 * 1024;
+
+impl NetMode {
+    /// `--host [port]` runs the game for others to join, `--join <address>` joins one
+    pub fn from_args() -> NetMode {
+        let mut args = env::args().skip(1).peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--host" => {
+                    let port = args
+                        .peek()
+                        .and_then(|port| port.parse().ok())
+                        .unwrap_or(DEFAULT_PORT);
+                    return NetMode::Host(port);
+                }
+                "--join" => {
+                    let Some(address) = args.next() else {
+                        println!("--join needs the address of the host");
+                        continue;
+                    };
+                    // The port may be left out
+                    let address = if address.contains(':') {
+                        address
+                    } else {
+                        format!("{}:{}", address, DEFAULT_PORT)
+                    };
+                    match address.to_socket_addrs().ok().and_then(|mut found| found.next()) {
+                        Some(host) => return NetMode::Client(host),
+                        None => println!("Could not find host {}", address),
+                    }
+                }
+                _ => {}
+            }
+        }
+        NetMode::Off
+    }
+}
+
+impl NetSocket {
+    /// The host listens on `port` on every interface, clients take any free port
+    pub fn bind(port: u16) -> Option<NetSocket> {
+        let socket = match UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port))) {
+            Ok(socket) => socket,
+            Err(error) => {
+                println!("Could not open port {}: {}", port, error);
+                return None;
+            }
+        };
+        if let Err(error) = socket.set_nonblocking(true) {
+            println!("Could not set up the socket: {}", error);
+            return None;
+        }
+        Some(NetSocket(socket))
+    }
+
+    pub fn send<T: Serialize>(&self, to: SocketAddr, message: &T) {
+        let bytes = match bincode::serialize(message) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                println!("Could not encode message: {}", error);
+                return;
+            }
+        };
+        if bytes.len() > MAX_MESSAGE {
+            println!(
+                "Message to {} is {} bytes, over the {} that fit in one packet, and may get lost",
+                to,
+                bytes.len(),
+                MAX_MESSAGE
+            );
+        }
+        // Lost datagrams are fine, the next one carries everything again
+        if let Err(error) = self.0.send_to(&bytes, to) {
+            if error.kind() != ErrorKind::WouldBlock {
+                println!("Could not send to {}: {}", to, error);
+            }
+        }
+    }
+
+    /// Every message waiting on the socket, dropping any that don't decode
+    pub fn receive<T: DeserializeOwned>(&self) -> Vec<(SocketAddr, T)> {
+        let mut buffer = vec![0; MAX_DATAGRAM];
+        let mut messages = Vec::new();
+        loop {
+            match self.0.recv_from(&mut buffer) {
+                Ok((length, from)) => match bincode::deserialize(&buffer[..length]) {
+                    Ok(message) => messages.push((from, message)),
+                    Err(error) => println!("Dropped a message from {}: {}", from, error),
+                },
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                // Windows reports datagrams that didn't arrive on the next read, nothing to do about it
+                Err(error) if error.kind() == ErrorKind::ConnectionReset => continue,
+                Err(error) => {
+                    println!("Could not receive: {}", error);
+                    break;
+                }
+            }
+        }
+        messages
+    }
+}
@@ -0,0 +1,362 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{AppState, FIXED_TIMESTEP};
+use crate::components::{Bullet, ColliderBundle, Grenade, Health, StateScoped, Weapon};
+use crate::game::campaign::components::CampaignProgress;
+use crate::game::enemies::components::{Enemy, Zombie};
+use crate::game::player::components::{InputDevice, Player, PlayerActions, PlayerId};
+use crate::game::player::systems::{spawn_player_entity, PLAYER_SPEED};
+use crate::graphics::CharacterSheet;
+use crate::net::components::*;
+use super::host::SNAPSHOT_EVERY;
+
+/// Seconds without a word from the host before giving up on it
+const HOST_TIMEOUT: f32 = 5.0;
+/// Our own player is put where the host has it once they are this far apart
+const RECONCILE_DISTANCE: f32 = 48.0;
+const SNAPSHOT_INTERVAL: f32 = SNAPSHOT_EVERY as f32 * FIXED_TIMESTEP;
+
+/// Asks to join until the host answers
+pub fn say_hello(
+    time: Res<Time>,
+    socket: Res<NetSocket>,
+    mut session: ResMut<ClientSession>,
+) {
+    if session.slot.is_some() {
+        return;
+    }
+    session.hello_timer.tick(time.delta());
+    if session.hello_timer.just_finished() {
+        println!("Joining {}", session.host);
+        socket.send(session.host, &ClientMessage::Hello { version: NET_VERSION });
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn receive_host_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    socket: Res<NetSocket>,
+    mut session: ResMut<ClientSession>,
+    app_state: Res<State<AppState>>,
+    asset_server: Res<AssetServer>,
+    characters: Res<CharacterSheet>,
+    mut replicated_query: Query<
+        (&mut Transform, Option<&mut NetInterpolation>, Option<&mut Health>, Option<&mut Weapon>),
+        With<NetId>
+    >,
+) {
+    session.silence += time.delta_seconds();
+    let mut snapshots: Vec<Snapshot> = Vec::new();
+    for (from, message) in socket.receive::<ServerMessage>() {
+        if from != session.host {
+            continue;
+        }
+        session.silence = 0.0;
+        match message {
+            ServerMessage::Welcome { slot } => {
+                if session.slot.is_none() {
+                    println!("Joined as player {}", slot + 1);
+                    // The host may have restarted and counts ticks from the start again
+                    session.tick = 0;
+                }
+                session.slot = Some(slot);
+            }
+            ServerMessage::Refused { reason } => println!("Could not join: {}", reason),
+            // Late parts of the newest snapshot still count, older snapshots don't
+            ServerMessage::Snapshot(snapshot) => {
+                if snapshot.tick >= session.tick {
+                    snapshots.push(snapshot);
+                }
+            }
+        }
+    }
+    if session.slot.is_some() && session.silence > HOST_TIMEOUT {
+        println!("Lost the host");
+        session.slot = None;
+        session.state = HostState::Menu;
+    }
+
+    let Some(newest) = snapshots.iter().max_by_key(|snapshot| snapshot.tick) else {
+        return;
+    };
+    session.tick = newest.tick;
+    session.state = newest.state;
+    session.world = newest.world;
+
+    // Replicated entities only live in the game and went away with it
+    if app_state.0 != AppState::Game {
+        session.entities.clear();
+        return;
+    }
+
+    let tick = session.tick;
+    if session.parts.tick != tick {
+        session.parts = SnapshotParts {
+            tick,
+            ..default()
+        };
+    }
+    let mut complete = false;
+    for snapshot in snapshots.into_iter().filter(|snapshot| snapshot.tick == tick) {
+        if !session.parts.received.insert(snapshot.part) {
+            continue;
+        }
+        complete = session.parts.received.len() as u32 >= snapshot.parts;
+        for net_entity in snapshot.entities {
+            apply_net_entity(&mut commands, &mut session, &mut replicated_query, &asset_server, &characters, net_entity);
+        }
+    }
+    if !complete {
+        return;
+    }
+
+    let session = &mut *session;
+    let seen = &session.parts.seen;
+    session.entities.retain(|id, entity| {
+        if seen.contains(id) {
+            return true;
+        }
+        commands.entity(*entity).despawn_recursive();
+        false
+    });
+}
+
+/// Moves, heals and reloads a replicated entity as one part of a snapshot has it,
+/// spawning it if it is new
+#[allow(clippy::type_complexity)]
+fn apply_net_entity(
+    commands: &mut Commands,
+    session: &mut ClientSession,
+    replicated_query: &mut Query<
+        (&mut Transform, Option<&mut NetInterpolation>, Option<&mut Health>, Option<&mut Weapon>),
+        With<NetId>
+    >,
+    asset_server: &AssetServer,
+    characters: &CharacterSheet,
+    net_entity: NetEntity,
+) {
+    session.parts.seen.insert(net_entity.id);
+    let existing = session
+        .entities
+        .get(&net_entity.id)
+        .and_then(|entity| replicated_query.get_mut(*entity).ok());
+    let Some((mut transform, interpolation, health, weapon)) = existing else {
+        let entity = spawn_replicated(commands, asset_server, characters, &net_entity, session.slot);
+        session.entities.insert(net_entity.id, entity);
+        return;
+    };
+
+    match interpolation {
+        Some(mut interpolation) => {
+            *interpolation = NetInterpolation {
+                from: transform.translation.truncate(),
+                to: net_entity.position,
+                elapsed: 0.0,
+            };
+        }
+        // Our own player, which `predict_own_player` keeps ahead of the host
+        None => {
+            if transform.translation.truncate().distance(net_entity.position) > RECONCILE_DISTANCE {
+                transform.translation = net_entity.position.extend(transform.translation.z);
+            }
+        }
+    }
+    if let (Some(mut health), Some(health_points)) = (health, net_entity.health) {
+        health.health_points = health_points;
+    }
+    if let (Some(mut weapon), Some(net_weapon)) = (weapon, net_entity.weapon) {
+        weapon.ammo.bullets = net_weapon.bullets;
+        weapon.mag_size = net_weapon.mag_size;
+        weapon.reloading = net_weapon.reloading;
+    }
+}
+
+/// Draws what the host has, the host's physics and AI decide where it goes
+fn spawn_replicated(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    characters: &CharacterSheet,
+    net_entity: &NetEntity,
+    own_slot: Option<usize>,
+) -> Entity {
+    let transform = Transform::from_translation(net_entity.position.extend(0.0));
+    let entity = match net_entity.kind {
+        NetKind::Player { slot } => {
+            let device = if own_slot == Some(slot) {
+                InputDevice::KeyboardMouse
+            } else {
+                InputDevice::Remote(slot)
+            };
+            let player = spawn_player_entity(commands, characters, transform.translation, PlayerId(slot), device);
+            commands.entity(player).remove::<ColliderBundle>().remove::<ActiveEvents>();
+            player
+        }
+        NetKind::Zombie => commands
+            .spawn((
+                SpriteBundle {
+                    texture: asset_server.load("zombie.png"),
+                    transform,
+                    ..default()
+                },
+                Enemy,
+                Zombie,
+                Health {
+                    health_points: net_entity.health.unwrap_or_default(),
+                },
+            ))
+            .id(),
+        NetKind::Bullet => {
+            let angle = net_entity.velocity.y.atan2(net_entity.velocity.x);
+            commands
+                .spawn((
+                    SpriteBundle {
+                        texture: asset_server.load("bullet.png"),
+                        transform: transform.with_rotation(Quat::from_rotation_z(angle)),
+                        ..default()
+                    },
+                    Bullet,
+                ))
+                .id()
+        }
+        NetKind::Grenade => commands
+            .spawn((
+                SpriteBundle {
+                    texture: asset_server.load("granade.png"),
+                    transform,
+                    ..default()
+                },
+                Grenade,
+            ))
+            .id(),
+    };
+    commands.entity(entity).insert((net_entity.id, StateScoped(AppState::Game)));
+    if !matches!(net_entity.kind, NetKind::Player { slot } if own_slot == Some(slot)) {
+        commands.entity(entity).insert(NetInterpolation {
+            from: net_entity.position,
+            to: net_entity.position,
+            elapsed: 0.0,
+        });
+    }
+    entity
+}
+
+/// Goes where the host's game is: into its world, to Game Over with it or back to the menu
+pub fn follow_host(
+    session: Res<ClientSession>,
+    progress: Res<CampaignProgress>,
+    app_state: Res<State<AppState>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    if session.slot.is_none() {
+        if app_state.0 == AppState::Game {
+            app_state_next_state.set(AppState::MainMenu);
+        }
+        return;
+    }
+    match (session.state, app_state.0) {
+        (HostState::Playing, AppState::MainMenu | AppState::GameOver) => {
+            app_state_next_state.set(AppState::Loading);
+        }
+        (HostState::Playing, AppState::Game) if progress.world != session.world => {
+            app_state_next_state.set(AppState::Loading);
+        }
+        (HostState::GameOver, AppState::Game) => app_state_next_state.set(AppState::GameOver),
+        (HostState::Menu, AppState::Game) => app_state_next_state.set(AppState::MainMenu),
+        _ => {}
+    }
+}
+
+/// Loads the world the host plays in, whatever got us to the loading screen
+pub fn enter_host_world(
+    session: Res<ClientSession>,
+    mut progress: ResMut<CampaignProgress>,
+) {
+    if progress.world != session.world {
+        progress.world = session.world;
+        progress.level = None;
+    }
+}
+
+/// Players, zombies and the rest only come from the host, the ones the map or menus spawn here go
+#[allow(clippy::type_complexity)]
+pub fn despawn_unreplicated(
+    mut commands: Commands,
+    entity_query: Query<
+        Entity,
+        (Without<NetId>, Or<(Added<Player>, Added<Zombie>, Added<Bullet>, Added<Grenade>)>)
+    >,
+) {
+    for entity in entity_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Sends what our player wants to do every frame, which also tells the host we're still here
+pub fn send_input(
+    socket: Res<NetSocket>,
+    session: Res<ClientSession>,
+    mut player_query: Query<(&PlayerId, &mut PlayerActions), With<Player>>,
+) {
+    let Some(slot) = session.slot else {
+        return;
+    };
+    let own_player = player_query
+        .iter_mut()
+        .find(|(player_id, _)| player_id.0 == slot);
+    let actions = match own_player {
+        Some((_, mut actions)) => {
+            let sent = actions.clone();
+            // Presses are sent once, a lost one is lost
+            actions.fire_just_pressed = false;
+            actions.reload = false;
+            actions.throw_grenade = false;
+            sent
+        }
+        None => PlayerActions::default(),
+    };
+    socket.send(session.host, &ClientMessage::Input { ack: session.tick, actions });
+}
+
+/// Moves our own player right away instead of waiting for the host to
+#[allow(clippy::type_complexity)]
+pub fn predict_own_player(
+    time: Res<Time>,
+    session: Res<ClientSession>,
+    mut player_query: Query<(&PlayerId, &PlayerActions, &Weapon, &mut Transform), (With<Player>, Without<NetInterpolation>)>,
+) {
+    for (player_id, actions, weapon, mut transform) in player_query.iter_mut() {
+        if session.slot != Some(player_id.0) {
+            continue;
+        }
+        let speed = if weapon.reloading { PLAYER_SPEED / 2.0 } else { PLAYER_SPEED };
+        transform.translation += (actions.movement * speed * time.delta_seconds()).extend(0.0);
+    }
+}
+
+pub fn interpolate_replicated(
+    time: Res<Time>,
+    mut entity_query: Query<(&mut Transform, &mut NetInterpolation)>,
+) {
+    for (mut transform, mut interpolation) in entity_query.iter_mut() {
+        interpolation.elapsed += time.delta_seconds();
+        let progress = (interpolation.elapsed / SNAPSHOT_INTERVAL).min(1.0);
+        transform.translation = interpolation
+            .from
+            .lerp(interpolation.to, progress)
+            .extend(transform.translation.z);
+    }
+}
+
+/// Lets the host know right away when the game is closed
+pub fn say_goodbye(
+    mut exit_events: EventReader<AppExit>,
+    socket: Res<NetSocket>,
+    session: Res<ClientSession>,
+) {
+    if exit_events.iter().count() > 0 && session.slot.is_some() {
+        socket.send(session.host, &ClientMessage::Bye);
+    }
+}
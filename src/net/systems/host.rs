@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{AppState, FIXED_TIMESTEP};
+use crate::components::{Bullet, Grenade, Health, Shooter, Weapon};
+use crate::game::campaign::components::CampaignProgress;
+use crate::game::enemies::components::Zombie;
+use crate::game::player::components::{InputDevice, Player, PlayerActions, PlayerId};
+use crate::game::player::resources::{PlayerSpawns, Respawns};
+use crate::game::player::systems::spawn_player_at;
+use crate::graphics::CharacterSheet;
+use crate::net::components::*;
+
+/// Players a host takes in besides its own
+const MAX_CLIENTS: usize = 3;
+/// Seconds without a word before a client counts as gone
+const CLIENT_TIMEOUT: f32 = 5.0;
+/// Fixed steps between snapshots, 30 a second
+pub const SNAPSHOT_EVERY: u32 = 2;
+/// Bullets of remote players are moved ahead by at most this many seconds
+const MAX_COMPENSATION: f32 = 0.25;
+
+/// Every player, zombie, bullet and grenade gets an id the clients know it by
+#[allow(clippy::type_complexity)]
+pub fn assign_net_ids(
+    mut commands: Commands,
+    mut host_tick: ResMut<HostTick>,
+    entity_query: Query<
+        Entity,
+        (Without<NetId>, Or<(With<Player>, With<Zombie>, With<Bullet>, With<Grenade>)>)
+    >,
+) {
+    for entity in entity_query.iter() {
+        commands.entity(entity).insert(NetId(host_tick.next_id));
+        host_tick.next_id += 1;
+    }
+}
+
+pub fn receive_client_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    socket: Res<NetSocket>,
+    mut connections: ResMut<Connections>,
+    mut respawns: ResMut<Respawns>,
+    mut player_query: Query<(Entity, &PlayerId, &mut PlayerActions), With<Player>>,
+) {
+    for connection in connections.clients.values_mut() {
+        connection.silence += time.delta_seconds();
+    }
+
+    for (from, message) in socket.receive::<ClientMessage>() {
+        match message {
+            ClientMessage::Hello { version } => {
+                if let Some(connection) = connections.clients.get(&from) {
+                    // Our welcome got lost
+                    socket.send(from, &ServerMessage::Welcome { slot: connection.slot });
+                    continue;
+                }
+                if version != NET_VERSION {
+                    socket.send(from, &ServerMessage::Refused {
+                        reason: format!("the host runs version {}, not {}", NET_VERSION, version),
+                    });
+                    continue;
+                }
+                if connections.clients.len() >= MAX_CLIENTS {
+                    socket.send(from, &ServerMessage::Refused { reason: "the game is full".to_string() });
+                    continue;
+                }
+                // Slot 0 is the host's own keyboard and mouse player, even before it spawned
+                let mut slot = 1;
+                while player_query.iter().any(|(_, id, _)| id.0 == slot)
+                    || connections.clients.values().any(|connection| connection.slot == slot)
+                {
+                    slot += 1;
+                }
+                println!("Player {} joined from {}", slot + 1, from);
+                connections.clients.insert(from, Connection {
+                    slot,
+                    silence: 0.0,
+                    ack: 0,
+                    needs_spawn: true,
+                });
+                socket.send(from, &ServerMessage::Welcome { slot });
+            }
+            ClientMessage::Input { ack, actions: input } => {
+                let Some(connection) = connections.clients.get_mut(&from) else {
+                    continue;
+                };
+                connection.silence = 0.0;
+                connection.ack = connection.ack.max(ack);
+                let slot = connection.slot;
+                for (_, player_id, mut actions) in player_query.iter_mut() {
+                    if player_id.0 == slot {
+                        apply_remote_actions(&mut actions, &input);
+                    }
+                }
+            }
+            ClientMessage::Bye => {
+                if let Some(connection) = connections.clients.get_mut(&from) {
+                    // Dropped below with the ones that timed out
+                    connection.silence = CLIENT_TIMEOUT;
+                }
+            }
+        }
+    }
+
+    connections.clients.retain(|address, connection| {
+        if connection.silence < CLIENT_TIMEOUT {
+            return true;
+        }
+        println!("Player {} at {} left", connection.slot + 1, address);
+        for (player, player_id, _) in player_query.iter() {
+            if player_id.0 == connection.slot {
+                commands.entity(player).despawn_recursive();
+            }
+        }
+        respawns.pending.retain(|respawn| respawn.player_id.0 != connection.slot);
+        false
+    });
+}
+
+/// Presses add up until the fixed steps used them, like `read_player_input` does for local players
+fn apply_remote_actions(actions: &mut PlayerActions, input: &PlayerActions) {
+    actions.movement = input.movement;
+    actions.aim = input.aim;
+    actions.fire = input.fire;
+    actions.fire_just_pressed |= input.fire_just_pressed;
+    actions.reload |= input.reload;
+    actions.throw_grenade |= input.throw_grenade;
+    actions.talk = input.talk;
+    actions.interact = input.interact;
+    actions.menu_up = input.menu_up;
+    actions.menu_down = input.menu_down;
+}
+
+/// Remote players come back with every new map, like the local ones do in `spawn_player`
+pub fn respawn_remote_players_on_load(
+    mut connections: ResMut<Connections>,
+) {
+    for connection in connections.clients.values_mut() {
+        connection.needs_spawn = true;
+    }
+}
+
+pub fn spawn_remote_players(
+    mut commands: Commands,
+    mut connections: ResMut<Connections>,
+    player_spawns: Res<PlayerSpawns>,
+    player_query: Query<&PlayerId, With<Player>>,
+    characters: Res<CharacterSheet>,
+) {
+    for connection in connections.clients.values_mut() {
+        if !connection.needs_spawn {
+            continue;
+        }
+        if player_query.iter().any(|player_id| player_id.0 == connection.slot) {
+            connection.needs_spawn = false;
+            continue;
+        }
+        let player_id = PlayerId(connection.slot);
+        // Remote players have no spawn of their own in the map, they share one
+        let Some(spawn_point) = player_spawns
+            .points
+            .get(&player_id)
+            .or_else(|| player_spawns.points.values().next()) else {
+            continue;
+        };
+        spawn_player_at(&mut commands, &characters, spawn_point, player_id, InputDevice::Remote(connection.slot));
+        connection.needs_spawn = false;
+    }
+}
+
+/// Basic lag compensation. A client sees the game some time after it happened and its input takes
+/// time to get here, so bullets of remote players are moved ahead by that much to land where they aimed.
+pub fn fast_forward_remote_bullets(
+    host_tick: Res<HostTick>,
+    connections: Res<Connections>,
+    mut bullet_query: Query<(&mut Transform, &Velocity, &Shooter), Added<Bullet>>,
+    player_query: Query<&InputDevice, With<Player>>,
+) {
+    for (mut bullet_transform, velocity, shooter) in bullet_query.iter_mut() {
+        let Ok(InputDevice::Remote(slot)) = player_query.get(shooter.0) else {
+            continue;
+        };
+        let Some(connection) = connections.clients.values().find(|connection| connection.slot == *slot) else {
+            continue;
+        };
+        let behind = (host_tick.tick.saturating_sub(connection.ack) as f32 * FIXED_TIMESTEP).min(MAX_COMPENSATION);
+        bullet_transform.translation += (velocity.linvel * behind).extend(0.0);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn send_snapshots(
+    mut host_tick: ResMut<HostTick>,
+    socket: Res<NetSocket>,
+    connections: Res<Connections>,
+    app_state: Res<State<AppState>>,
+    progress: Res<CampaignProgress>,
+    entity_query: Query<(
+        &NetId,
+        &Transform,
+        Option<&Velocity>,
+        Option<&Health>,
+        Option<&Weapon>,
+        Option<&PlayerId>,
+        Option<&Zombie>,
+        Option<&Bullet>,
+    )>,
+) {
+    host_tick.tick += 1;
+    if host_tick.tick % SNAPSHOT_EVERY != 0 || connections.clients.is_empty() {
+        return;
+    }
+
+    let state = match app_state.0 {
+        AppState::MainMenu => HostState::Menu,
+        AppState::Loading | AppState::Game => HostState::Playing,
+        AppState::GameOver => HostState::GameOver,
+    };
+    let entities = entity_query
+        .iter()
+        .map(|(id, transform, velocity, health, weapon, player_id, zombie, bullet)| NetEntity {
+            id: *id,
+            kind: match (player_id, zombie, bullet) {
+                (Some(player_id), _, _) => NetKind::Player { slot: player_id.0 },
+                (_, Some(_), _) => NetKind::Zombie,
+                (_, _, Some(_)) => NetKind::Bullet,
+                _ => NetKind::Grenade,
+            },
+            position: transform.translation.truncate(),
+            velocity: velocity.map_or(Vec2::ZERO, |velocity| velocity.linvel),
+            health: health.map(|health| health.health_points),
+            weapon: weapon.map(|weapon| NetWeapon {
+                bullets: weapon.ammo.bullets,
+                mag_size: weapon.mag_size,
+                reloading: weapon.reloading,
+            }),
+        })
+        .collect();
+    for snapshot in split_snapshot(host_tick.tick, state, progress.world, entities) {
+        let message = ServerMessage::Snapshot(snapshot);
+        for address in connections.clients.keys() {
+            socket.send(*address, &message);
+        }
+    }
+}
+
+/// Splits the entities of one snapshot into as many parts as it takes for each to fit in a message
+pub fn split_snapshot(tick: u32, state: HostState, world: usize, entities: Vec<NetEntity>) -> Vec<Snapshot> {
+    let empty = ServerMessage::Snapshot(Snapshot {
+        tick,
+        part: 0,
+        parts: 0,
+        state,
+        world,
+        entities: Vec::new(),
+    });
+    let header = bincode::serialized_size(&empty).unwrap_or_default() as usize;
+
+    let mut groups = Vec::new();
+    let mut group = Vec::new();
+    let mut size = header;
+    for entity in entities {
+        let entity_size = bincode::serialized_size(&entity).unwrap_or_default() as usize;
+        if size + entity_size > MAX_MESSAGE && !group.is_empty() {
+            groups.push(std::mem::take(&mut group));
+            size = header;
+        }
+        size += entity_size;
+        group.push(entity);
+    }
+    groups.push(group);
+
+    let parts = groups.len() as u32;
+    groups
+        .into_iter()
+        .enumerate()
+        .map(|(part, entities)| Snapshot {
+            tick,
+            part: part as u32,
+            parts,
+            state,
+            world,
+            entities,
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::net::components::NetMode;
+
+pub mod client;
+pub mod host;
+
+/// Clients only draw what the host simulates
+pub fn not_client(mode: Res<NetMode>) -> bool {
+    !matches!(*mode, NetMode::Client(_))
+}
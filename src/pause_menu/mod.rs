@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+mod components;
+mod styles;
+mod systems;
+
+use crate::{AppState, SimulationState};
+
+use self::systems::{
+    layout::{spawn_pause_menu, despawn_pause_menu},
+    interactions::{toggle_pause, interact_with_resume_button, interact_with_save_game_button, interact_with_settings_button, interact_with_quit_to_menu_button},
+    physics::{pause_physics, resume_physics},
+};
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        //OnEnter State Systems
+        .add_systems(
+            (
+                spawn_pause_menu,
+                pause_physics,
+            ).in_schedule(OnEnter(SimulationState::Paused))
+        )
+        //Systems
+        .add_system(toggle_pause.in_set(OnUpdate(AppState::Game)))
+        .add_systems(
+            (
+                interact_with_resume_button,
+                interact_with_save_game_button,
+                interact_with_settings_button,
+                interact_with_quit_to_menu_button
+            ).in_set(OnUpdate(SimulationState::Paused))
+        )
+        //OnExit State Systems
+        .add_systems(
+            (
+                despawn_pause_menu,
+                resume_physics,
+            ).in_schedule(OnExit(SimulationState::Paused))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+use crate::{AppState, SimulationState};
+use crate::pause_menu::components::*;
+use crate::pause_menu::styles::*;
+use crate::save::components::{SaveRequested, SaveSlot};
+use crate::settings::components::SettingsState;
+use crate::systems::ButtonInteraction;
+
+/// Escape or select on any gamepad pauses and resumes the game
+pub fn toggle_pause(
+    keyboard: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    simulation_state: Res<State<SimulationState>>,
+    settings_state: Res<State<SettingsState>>,
+    mut simulation_state_next_state: ResMut<NextState<SimulationState>>,
+) {
+    // Escape belongs to the settings screen while it is open
+    if settings_state.0 == SettingsState::Open {
+        return;
+    }
+    let pressed = keyboard.just_pressed(KeyCode::Escape)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Select)));
+    if !pressed {
+        return;
+    }
+    match simulation_state.0 {
+        SimulationState::Running => simulation_state_next_state.set(SimulationState::Paused),
+        SimulationState::Paused => simulation_state_next_state.set(SimulationState::Running),
+    }
+}
+
+pub fn interact_with_resume_button(
+    mut button_query: ButtonInteraction<ResumeButton>,
+    mut simulation_state_next_state: ResMut<NextState<SimulationState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                simulation_state_next_state.set(SimulationState::Running);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_save_game_button(
+    mut button_query: ButtonInteraction<SaveGameButton>,
+    mut save_events: EventWriter<SaveRequested>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                save_events.send(SaveRequested { slot: SaveSlot::Quick });
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_settings_button(
+    mut button_query: ButtonInteraction<SettingsButton>,
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                settings_state_next_state.set(SettingsState::Open);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_quit_to_menu_button(
+    mut button_query: ButtonInteraction<QuitToMenuButton>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut simulation_state_next_state: ResMut<NextState<SimulationState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                app_state_next_state.set(AppState::MainMenu);
+                simulation_state_next_state.set(SimulationState::Running);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
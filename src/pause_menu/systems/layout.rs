@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use crate::pause_menu::components::*;
+use crate::pause_menu::styles::*;
+
+pub fn spawn_pause_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>
+) {
+    build_pause_menu(&mut commands, &asset_server);
+}
+
+pub fn despawn_pause_menu(
+    mut commands: Commands,
+    pause_menu_query: Query<Entity, With<PauseMenu>>
+) {
+    if let Ok(pause_menu_entity) = pause_menu_query.get_single() {
+        commands.entity(pause_menu_entity).despawn_recursive();
+    }
+}
+
+fn build_button<T: Component>(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    label: &str,
+    marker: T,
+) {
+    parent.spawn(
+        (
+            ButtonBundle {
+                style: NORMAL_BUTTON_STYLE,
+                background_color: NORMAL_BUTTON_COLOR.into(),
+                ..default()
+            },
+            marker
+        )
+    )
+    .with_children(|parent|{
+        parent.spawn(
+            TextBundle{
+                text: Text {
+                    sections: vec![
+                        TextSection::new(
+                            label,
+                            get_button_text_style(asset_server)
+                            )
+                    ],
+                    alignment: TextAlignment::Center,
+                    ..default()
+                },
+                ..default()
+            }
+        );
+    });
+}
+
+pub fn build_pause_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>
+) -> Entity {
+    let pause_menu_entity = commands.spawn(
+        (
+            NodeBundle {
+            style: PAUSE_MENU_STYLE,
+            background_color: PAUSE_MENU_BACKGROUND_COLOR.into(),
+            ..default()
+        },
+        PauseMenu{},
+    )
+    )
+    .with_children(|parent| {
+        //title
+        parent.spawn(
+            NodeBundle {
+                style: TITLE_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            parent.spawn(
+                TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Paused",
+                                get_title_text_style(asset_server)
+                            )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        build_button(parent, asset_server, "Resume", ResumeButton{});
+        build_button(parent, asset_server, "Save Game", SaveGameButton{});
+        build_button(parent, asset_server, "Settings", SettingsButton{});
+        build_button(parent, asset_server, "Quit to Menu", QuitToMenuButton{});
+    })
+    .id();
+
+    pause_menu_entity
+}
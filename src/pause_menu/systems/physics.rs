@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub fn pause_physics(
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    rapier_config.physics_pipeline_active = false;
+}
+
+pub fn resume_physics(
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    rapier_config.physics_pipeline_active = true;
+}
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::player::components::PlayerActions;
+
+/// Bumped whenever `Replay` changes shape, replays of other versions are not played
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Picked on the command line with `--record <file>` or `--replay <file>`
+#[derive(Resource, Clone, PartialEq, Debug, Default)]
+pub enum ReplayMode {
+    #[default]
+    Off,
+    Record(PathBuf),
+    Playback(PathBuf),
+}
+
+/// Actions of every player in one gameplay frame
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    /// By player slot
+    pub players: Vec<(usize, PlayerActions)>,
+}
+
+/// Player input of a run from its start, frame by frame. Paused frames and loading screens are left out.
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    /// Fixed timestep of the recording, played back with the same one
+    pub timestep: f32,
+    pub frames: Vec<ReplayFrame>,
+}
+
+/// Next frame to play back
+#[derive(Resource, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct ReplayCursor {
+    pub frame: usize,
+}
@@ -0,0 +1,72 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ron::ser::PrettyConfig;
+
+use super::components::{Replay, ReplayMode, REPLAY_VERSION};
+
+impl ReplayMode {
+    /// `--record <file>` writes a replay of every run, `--replay <file>` plays one back
+    pub fn from_args() -> ReplayMode {
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mode: fn(PathBuf) -> ReplayMode = match arg.as_str() {
+                "--record" => ReplayMode::Record,
+                "--replay" => ReplayMode::Playback,
+                _ => continue,
+            };
+            match args.next() {
+                Some(path) => return mode(PathBuf::from(path)),
+                None => println!("{} needs a file", arg),
+            }
+        }
+        ReplayMode::Off
+    }
+}
+
+impl Replay {
+    pub fn read(path: &Path) -> Option<Replay> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) => {
+                println!("Could not read {}: {}", path.display(), error);
+                return None;
+            }
+        };
+        let replay: Replay = match ron::from_str(&text) {
+            Ok(replay) => replay,
+            Err(error) => {
+                println!("Could not read {}: {}", path.display(), error);
+                return None;
+            }
+        };
+        if replay.version != REPLAY_VERSION {
+            println!(
+                "{} is a version {} replay, only version {} can be played",
+                path.display(),
+                replay.version,
+                REPLAY_VERSION
+            );
+            return None;
+        }
+        Some(replay)
+    }
+
+    pub fn write(&self, path: &Path) {
+        // One frame per line, pretty printing every action would make the files huge
+        let config = PrettyConfig::default().depth_limit(2);
+        let text = match ron::ser::to_string_pretty(self, config) {
+            Ok(text) => text,
+            Err(error) => {
+                println!("Could not write replay: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = fs::write(path, text) {
+            println!("Could not write {}: {}", path.display(), error);
+            return;
+        }
+        println!("Saved {} frames to {}", self.frames.len(), path.display());
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+pub mod components;
+pub mod file;
+pub(crate) mod systems;
+
+use crate::{AppState, SimulationState, FIXED_TIMESTEP};
+use crate::game::player::input::read_player_input;
+
+use self::components::{Replay, ReplayCursor, ReplayMode, REPLAY_VERSION};
+use self::systems::*;
+
+/// Records the player input of a run, or plays a recorded one back through the gameplay systems.
+/// Either way time advances by exactly one fixed step per frame, so every frame's input meets the same step.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let mode = ReplayMode::from_args();
+        let replay = match &mode {
+            ReplayMode::Off => None,
+            ReplayMode::Record(_) => Some(Replay {
+                version: REPLAY_VERSION,
+                timestep: FIXED_TIMESTEP,
+                frames: Vec::new(),
+            }),
+            ReplayMode::Playback(path) => Replay::read(path),
+        };
+        // Without a replay the game runs as usual, on real time
+        let Some(replay) = replay else {
+            app.init_resource::<ReplayMode>();
+            return;
+        };
+
+        app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(replay.timestep)))
+        .insert_resource(FixedTime::new_from_secs(replay.timestep))
+        .insert_resource(mode)
+        .insert_resource(replay)
+        .init_resource::<ReplayCursor>()
+        .add_startup_system(use_fixed_timestep)
+        .add_startup_system(start_playback.run_if(is_playing_back))
+        .add_system(start_recording.run_if(is_recording).in_schedule(OnExit(AppState::MainMenu)))
+        .add_system(start_recording.run_if(is_recording).in_schedule(OnExit(AppState::GameOver)))
+        .add_system(save_recording.in_schedule(OnEnter(AppState::GameOver)))
+        .add_system(save_recording.in_schedule(OnEnter(AppState::MainMenu)))
+        .add_system(save_recording_on_exit.in_base_set(CoreSet::Last))
+        .add_system(
+            record_frame
+                .run_if(is_recording)
+                .after(read_player_input)
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+        )
+        .add_system(
+            play_frame
+                .run_if(is_playing_back)
+                .before(read_player_input)
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::AppState;
+use crate::game::player::components::{Player, PlayerActions, PlayerId};
+use crate::replay::components::*;
+use crate::save::components::PendingLoad;
+
+pub fn is_recording(mode: Res<ReplayMode>) -> bool {
+    matches!(*mode, ReplayMode::Record(_))
+}
+
+pub fn is_playing_back(mode: Res<ReplayMode>) -> bool {
+    matches!(*mode, ReplayMode::Playback(_))
+}
+
+/// Devices are only read when no replay drives the players
+pub fn not_playing_back(mode: Res<ReplayMode>) -> bool {
+    !is_playing_back(mode)
+}
+
+/// Physics steps with the timestep the replay was recorded with
+pub fn use_fixed_timestep(
+    replay: Res<Replay>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    rapier_config.timestep_mode = TimestepMode::Fixed {
+        dt: replay.timestep,
+        substeps: 1,
+    };
+}
+
+/// A replay starts a new run right away, like pressing Play
+pub fn start_playback(
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    app_state_next_state.set(AppState::Loading);
+}
+
+/// Every new run is recorded from its first frame, replacing the last one
+pub fn start_recording(
+    pending_load: Res<PendingLoad>,
+    mut replay: ResMut<Replay>,
+) {
+    if pending_load.0.is_some() {
+        println!("Replays always start from a new run, this loaded game won't play back the same");
+    }
+    replay.frames.clear();
+}
+
+pub fn record_frame(
+    mut replay: ResMut<Replay>,
+    player_query: Query<(&PlayerId, &PlayerActions), With<Player>>,
+) {
+    let mut players: Vec<(usize, PlayerActions)> = player_query
+        .iter()
+        .map(|(player_id, actions)| (player_id.0, actions.clone()))
+        .collect();
+    players.sort_by_key(|(slot, _)| *slot);
+    replay.frames.push(ReplayFrame { players });
+}
+
+pub fn play_frame(
+    replay: Res<Replay>,
+    mut cursor: ResMut<ReplayCursor>,
+    mut mode: ResMut<ReplayMode>,
+    mut player_query: Query<(&PlayerId, &mut PlayerActions), With<Player>>,
+) {
+    let Some(frame) = replay.frames.get(cursor.frame) else {
+        // Hand the players back to their devices
+        println!("Replay finished after {} frames", cursor.frame);
+        *mode = ReplayMode::Off;
+        return;
+    };
+    for (player_id, mut actions) in player_query.iter_mut() {
+        *actions = frame
+            .players
+            .iter()
+            .find(|(slot, _)| *slot == player_id.0)
+            .map(|(_, recorded)| recorded.clone())
+            .unwrap_or_default();
+    }
+    cursor.frame += 1;
+}
+
+pub fn save_recording(
+    mode: Res<ReplayMode>,
+    replay: Res<Replay>,
+) {
+    let ReplayMode::Record(path) = &*mode else {
+        return;
+    };
+    if !replay.frames.is_empty() {
+        replay.write(path);
+    }
+}
+
+/// Closing the game in the middle of a run keeps what was recorded so far
+pub fn save_recording_on_exit(
+    mut exit_events: EventReader<AppExit>,
+    mode: Res<ReplayMode>,
+    replay: Res<Replay>,
+) {
+    if exit_events.iter().count() > 0 {
+        save_recording(mode, replay);
+    }
+}
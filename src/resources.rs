@@ -3,4 +3,29 @@ use bevy::prelude::*;
 #[derive(Resource)]
 pub struct MouseLoc {
     pub loc: Vec2,
-}
\ No newline at end of file
+}
+
+/// World space bounds of every level that is spawned right now, by level iid
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LoadedLevels {
+    pub levels: Vec<(String, Rect)>,
+}
+
+impl LoadedLevels {
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.levels.iter().any(|(_, bounds)| bounds.contains(point))
+    }
+
+    pub fn bounds(&self, iid: &str) -> Option<Rect> {
+        self.levels
+            .iter()
+            .find(|(level_iid, _)| level_iid == iid)
+            .map(|(_, bounds)| *bounds)
+    }
+}
+
+/// How hard the camera shakes, from 0 to 1. Explosions add to it and it fades over time.
+#[derive(Resource, Copy, Clone, PartialEq, Debug, Default)]
+pub struct ScreenShake {
+    pub trauma: f32,
+}
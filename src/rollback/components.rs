@@ -0,0 +1,122 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::enemies::components::DamageSource;
+use crate::game::objectives::components::Objective;
+use crate::game::player::components::PlayerActions;
+use crate::game::stats::components::RunStats;
+
+/// Fixed steps kept to go back to, two seconds
+pub const SNAPSHOT_FRAMES: usize = 120;
+/// How far back the rewind key goes, in fixed steps
+pub const REWIND_TICKS: u32 = 60;
+
+/// Gameplay state of every player, zombie, buddy, bullet and grenade after fixed step `tick`
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub tick: u32,
+    /// Sorted by id
+    pub entities: Vec<EntitySnapshot>,
+    /// Or zombies killed again after a rewind would count twice
+    pub stats: Option<RunStats>,
+    /// Objectives of every level visited, by level iid
+    pub objectives: Option<HashMap<String, Vec<Objective>>>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct EntitySnapshot {
+    /// `RollbackId` of the entity, state goes back into whichever entity has it by then
+    pub id: u64,
+    /// What to spawn again if the entity is gone by then
+    pub kind: SnapshotKind,
+    pub translation: Vec3,
+    /// Around z, nothing turns any other way
+    pub angle: f32,
+    /// Linear and angular velocity
+    pub velocity: Option<(Vec2, f32)>,
+    pub health: Option<i32>,
+    pub max_health: Option<i32>,
+    pub weapon: Option<WeaponSnapshot>,
+    pub grenades: Option<u32>,
+    /// Seconds a grenade has been ticking
+    pub detonation: Option<f32>,
+    pub actions: Option<PlayerActions>,
+    pub last_hit: Option<DamageSource>,
+    pub buddy: Option<BuddySnapshot>,
+    pub bot: Option<BotSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum SnapshotKind {
+    Player {
+        slot: usize,
+    },
+    /// With the iid of the level entity it was spawned from
+    Zombie {
+        iid: Option<String>,
+    },
+    Buddy,
+    /// With the `RollbackId` of whoever fired it
+    Bullet {
+        shooter: Option<u64>,
+    },
+    Grenade,
+}
+
+/// Timers are kept as seconds elapsed
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct WeaponSnapshot {
+    pub bullets: u32,
+    pub mag_size: u32,
+    pub reloading: bool,
+    pub fire_rate: f32,
+    pub reload: f32,
+    /// Seconds between shots and for a reload, upgraded by weapon pickups
+    pub fire_rate_duration: f32,
+    pub reload_duration: f32,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BuddySnapshot {
+    /// `RollbackId` of the zombie it shoots at
+    pub target: Option<u64>,
+    /// Seconds spent waiting to be revived, `None` while standing
+    pub downed: Option<f32>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BotSnapshot {
+    pub waypoint: usize,
+    pub grenade_cooldown: f32,
+}
+
+/// Tells an entity apart across snapshots, also once a rewind spawned it again as another entity
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Component, Serialize, Deserialize)]
+pub struct RollbackId(pub u64);
+
+/// Next `RollbackId` given out
+#[derive(Resource, Copy, Clone, Debug, Default)]
+pub struct RollbackIds {
+    pub next_id: u64,
+}
+
+/// Encoded snapshots of the last `capacity` fixed steps of the game, oldest first
+#[derive(Resource, Clone, Debug)]
+pub struct SnapshotBuffer {
+    pub frames: VecDeque<(u32, Vec<u8>)>,
+    pub capacity: usize,
+    /// Tick the next snapshot is taken at, counted from the start of the game
+    pub next_tick: u32,
+}
+
+impl Default for SnapshotBuffer {
+    fn default() -> Self {
+        Self {
+            frames: VecDeque::with_capacity(SNAPSHOT_FRAMES),
+            capacity: SNAPSHOT_FRAMES,
+            next_tick: 0,
+        }
+    }
+}
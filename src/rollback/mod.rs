@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+pub mod components;
+pub mod snapshot;
+pub(crate) mod systems;
+
+use crate::{AppState, FixedGameplaySet, SimulationState};
+
+use self::components::{RollbackIds, SnapshotBuffer};
+use self::systems::*;
+
+/// Keeps snapshots of the gameplay state of the last fixed steps, to go back to one of them.
+/// Backspace rewinds a second while playing alone and without a replay.
+pub struct RollbackPlugin;
+
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .init_resource::<SnapshotBuffer>()
+        .init_resource::<RollbackIds>()
+        .add_system(
+            record_snapshot
+                .after(FixedGameplaySet)
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(SimulationState::Running))
+                .in_schedule(CoreSchedule::FixedUpdate)
+        )
+        .add_system(clear_snapshots.in_schedule(OnExit(AppState::Game)))
+        .add_system(
+            rewind_on_key
+                .run_if(can_rewind)
+                .in_set(OnUpdate(AppState::Game))
+                .in_set(OnUpdate(SimulationState::Running))
+        );
+    }
+}
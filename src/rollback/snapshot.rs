@@ -0,0 +1,418 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::components::{
+    Bullet, ColliderBundle, DetonationTimer, Grenade, Health, Interpolated, Inventory, MaxHealth, Shooter, Weapon,
+    ZombieBundle,
+};
+use crate::game::bot::components::BotBrain;
+use crate::game::buddy::components::{Buddy, BuddyTarget, Downed};
+use crate::game::enemies::components::{LastHit, Zombie};
+use crate::game::objectives::components::MissionTracker;
+use crate::game::player::components::{InputDevice, Player, PlayerActions, PlayerId};
+use crate::game::player::resources::{Lives, PlayerSpawns, Respawns, SpawnPoint};
+use crate::game::player::systems::{spawn_bullet, spawn_grenade, spawn_player_at};
+use crate::game::stats::components::RunStats;
+use crate::graphics::CharacterSheet;
+use crate::save::components::ZombieRecord;
+use super::components::*;
+
+impl WorldSnapshot {
+    pub fn capture(world: &mut World, tick: u32) -> WorldSnapshot {
+        assign_rollback_ids(world);
+        let ids: HashMap<Entity, u64> = world
+            .query::<(Entity, &RollbackId)>()
+            .iter(world)
+            .map(|(entity, id)| (entity, id.0))
+            .collect();
+
+        let mut entity_query = world.query_filtered::<(
+            &RollbackId,
+            (Option<&PlayerId>, Option<&EntityInstance>, Option<&Shooter>, Option<&Zombie>, Option<&Bullet>),
+            &Transform,
+            Option<&Velocity>,
+            (Option<&Health>, Option<&MaxHealth>),
+            Option<&Weapon>,
+            Option<&Inventory>,
+            Option<&DetonationTimer>,
+            Option<&PlayerActions>,
+            Option<&LastHit>,
+            Option<&Buddy>,
+            Option<&BuddyTarget>,
+            Option<&Downed>,
+            Option<&BotBrain>,
+        ), Or<(With<Player>, With<Zombie>, With<Buddy>, With<Bullet>, With<Grenade>)>>();
+
+        let mut entities: Vec<EntitySnapshot> = entity_query
+            .iter(world)
+            .map(|(id, kind, transform, velocity, (health, max_health), weapon, inventory, detonation, actions, last_hit, buddy, target, downed, bot)| {
+                let (player_id, entity_instance, shooter, zombie, bullet) = kind;
+                let kind = if buddy.is_some() {
+                    SnapshotKind::Buddy
+                } else if let Some(player_id) = player_id {
+                    SnapshotKind::Player { slot: player_id.0 }
+                } else if zombie.is_some() {
+                    SnapshotKind::Zombie { iid: entity_instance.map(|entity_instance| entity_instance.iid.clone()) }
+                } else if bullet.is_some() {
+                    SnapshotKind::Bullet { shooter: shooter.and_then(|shooter| ids.get(&shooter.0).copied()) }
+                } else {
+                    SnapshotKind::Grenade
+                };
+                EntitySnapshot {
+                    id: id.0,
+                    kind,
+                    translation: transform.translation,
+                    angle: transform.rotation.to_euler(EulerRot::ZYX).0,
+                    velocity: velocity.map(|velocity| (velocity.linvel, velocity.angvel)),
+                    health: health.map(|health| health.health_points),
+                    max_health: max_health.map(|max_health| max_health.health_points),
+                    weapon: weapon.map(|weapon| WeaponSnapshot {
+                        bullets: weapon.ammo.bullets,
+                        mag_size: weapon.mag_size,
+                        reloading: weapon.reloading,
+                        fire_rate: weapon.fire_rate.timer.elapsed_secs(),
+                        reload: weapon.reload_timer.reload_timer.elapsed_secs(),
+                        fire_rate_duration: weapon.fire_rate.timer.duration().as_secs_f32(),
+                        reload_duration: weapon.reload_timer.reload_timer.duration().as_secs_f32(),
+                    }),
+                    grenades: inventory.map(|inventory| inventory.grenades),
+                    detonation: detonation.map(|detonation| detonation.detonation_timer.elapsed_secs()),
+                    actions: actions.cloned(),
+                    last_hit: last_hit.map(|last_hit| last_hit.source),
+                    buddy: buddy.map(|_| BuddySnapshot {
+                        target: target
+                            .and_then(|target| target.zombie)
+                            .and_then(|zombie| ids.get(&zombie).copied()),
+                        downed: downed.map(|downed| downed.revive_timer.elapsed_secs()),
+                    }),
+                    bot: bot.map(|bot| BotSnapshot {
+                        waypoint: bot.waypoint,
+                        grenade_cooldown: bot.grenade_cooldown.elapsed_secs(),
+                    }),
+                }
+            })
+            .collect();
+        entities.sort_by_key(|snapshot| snapshot.id);
+
+        WorldSnapshot {
+            tick,
+            entities,
+            stats: world.get_resource::<RunStats>().copied(),
+            objectives: world.get_resource::<MissionTracker>().map(|tracker| tracker.levels.clone()),
+        }
+    }
+
+    /// Puts every entity back the way it was, spawning the ones that died since again, along with
+    /// the run stats and objective progress. Bullets and grenades thrown since are taken back.
+    /// Returns how many entities of the snapshot could not come back.
+    pub fn restore(&self, world: &mut World) -> usize {
+        if let (Some(stats), Some(mut run_stats)) = (self.stats, world.get_resource_mut::<RunStats>()) {
+            *run_stats = stats;
+        }
+        if let (Some(objectives), Some(mut tracker)) = (&self.objectives, world.get_resource_mut::<MissionTracker>()) {
+            tracker.levels = objectives.clone();
+        }
+
+        let kept: HashSet<u64> = self.entities.iter().map(|snapshot| snapshot.id).collect();
+        let newer: Vec<Entity> = world
+            .query_filtered::<(Entity, Option<&RollbackId>), Or<(With<Bullet>, With<Grenade>)>>()
+            .iter(world)
+            .filter(|(_, id)| !matches!(id, Some(id) if kept.contains(&id.0)))
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in newer {
+            world.despawn(entity);
+        }
+
+        let missing = self.respawn_missing(world);
+        let entities: HashMap<u64, Entity> = world
+            .query::<(Entity, &RollbackId)>()
+            .iter(world)
+            .map(|(entity, id)| (id.0, entity))
+            .collect();
+
+        for snapshot in self.entities.iter() {
+            let Some(mut entity_mut) = entities
+                .get(&snapshot.id)
+                .and_then(|entity| world.get_entity_mut(*entity)) else {
+                continue;
+            };
+
+            if let Some(mut transform) = entity_mut.get_mut::<Transform>() {
+                transform.translation = snapshot.translation;
+                transform.rotation = Quat::from_rotation_z(snapshot.angle);
+            }
+            // Drawn right where it is, not smoothed over from where it was
+            if let Some(mut interpolated) = entity_mut.get_mut::<Interpolated>() {
+                interpolated.previous = snapshot.translation;
+                interpolated.current = snapshot.translation;
+                interpolated.rendered = snapshot.translation;
+            }
+            if let (Some(mut velocity), Some((linvel, angvel))) = (entity_mut.get_mut::<Velocity>(), snapshot.velocity) {
+                velocity.linvel = linvel;
+                velocity.angvel = angvel;
+            }
+            if let (Some(mut health), Some(health_points)) = (entity_mut.get_mut::<Health>(), snapshot.health) {
+                health.health_points = health_points;
+            }
+            if let (Some(mut max_health), Some(health_points)) = (entity_mut.get_mut::<MaxHealth>(), snapshot.max_health) {
+                max_health.health_points = health_points;
+            }
+            if let (Some(mut weapon), Some(weapon_snapshot)) = (entity_mut.get_mut::<Weapon>(), snapshot.weapon) {
+                weapon.ammo.bullets = weapon_snapshot.bullets;
+                weapon.mag_size = weapon_snapshot.mag_size;
+                weapon.reloading = weapon_snapshot.reloading;
+                weapon.fire_rate.timer.set_duration(Duration::from_secs_f32(weapon_snapshot.fire_rate_duration));
+                weapon.reload_timer.reload_timer.set_duration(Duration::from_secs_f32(weapon_snapshot.reload_duration));
+                set_timer(&mut weapon.fire_rate.timer, weapon_snapshot.fire_rate);
+                set_timer(&mut weapon.reload_timer.reload_timer, weapon_snapshot.reload);
+            }
+            if let (Some(mut inventory), Some(grenades)) = (entity_mut.get_mut::<Inventory>(), snapshot.grenades) {
+                inventory.grenades = grenades;
+            }
+            if let (Some(mut detonation), Some(elapsed)) = (entity_mut.get_mut::<DetonationTimer>(), snapshot.detonation) {
+                set_timer(&mut detonation.detonation_timer, elapsed);
+            }
+            if let (Some(mut actions), Some(actions_snapshot)) = (entity_mut.get_mut::<PlayerActions>(), &snapshot.actions) {
+                *actions = actions_snapshot.clone();
+            }
+            if let (Some(mut last_hit), Some(source)) = (entity_mut.get_mut::<LastHit>(), snapshot.last_hit) {
+                last_hit.source = source;
+            }
+            if let Some(buddy) = snapshot.buddy {
+                if let Some(mut target) = entity_mut.get_mut::<BuddyTarget>() {
+                    target.zombie = buddy.target.and_then(|id| entities.get(&id).copied());
+                }
+                match buddy.downed {
+                    Some(elapsed) => {
+                        let mut downed = Downed::default();
+                        set_timer(&mut downed.revive_timer, elapsed);
+                        entity_mut.insert(downed);
+                    }
+                    None => {
+                        entity_mut.remove::<Downed>();
+                    }
+                }
+            }
+            if let (Some(mut bot), Some(bot_snapshot)) = (entity_mut.get_mut::<BotBrain>(), snapshot.bot) {
+                bot.waypoint = bot_snapshot.waypoint;
+                set_timer(&mut bot.grenade_cooldown, bot_snapshot.grenade_cooldown);
+            }
+        }
+        missing
+    }
+
+    /// Spawns the entities of the snapshot that are gone again, under their old `RollbackId`,
+    /// for `restore` to put their state back into. A player that respawned since takes its old id,
+    /// one waiting to respawn comes back with the life it lost. Buddies and players with no lives
+    /// left don't come back, returns how many of those there are.
+    fn respawn_missing(&self, world: &mut World) -> usize {
+        let mut present: HashMap<u64, Entity> = world
+            .query::<(Entity, &RollbackId)>()
+            .iter(world)
+            .map(|(entity, id)| (id.0, entity))
+            .collect();
+        let players: HashMap<usize, Entity> = world
+            .query_filtered::<(Entity, &PlayerId), With<Player>>()
+            .iter(world)
+            .map(|(entity, player_id)| (player_id.0, entity))
+            .collect();
+        let gone: Vec<&EntitySnapshot> = self
+            .entities
+            .iter()
+            .filter(|snapshot| !present.contains_key(&snapshot.id))
+            .collect();
+
+        let mut missing = 0;
+        let mut comebacks: HashMap<u64, (SpawnPoint, InputDevice)> = HashMap::new();
+        for snapshot in gone.iter() {
+            match &snapshot.kind {
+                SnapshotKind::Player { slot } => {
+                    if let Some(player) = players.get(slot) {
+                        world.entity_mut(*player).insert(RollbackId(snapshot.id));
+                        present.insert(snapshot.id, *player);
+                        continue;
+                    }
+                    // Its loadout carries the upgrades, the snapshot puts back the rest
+                    let Some(mut spawn_point) = world.get_resource::<PlayerSpawns>().and_then(|player_spawns| {
+                        player_spawns
+                            .points
+                            .get(&PlayerId(*slot))
+                            .or_else(|| player_spawns.points.values().next())
+                            .cloned()
+                    }) else {
+                        missing += 1;
+                        continue;
+                    };
+                    let Some(device) = take_pending_respawn(world, *slot) else {
+                        missing += 1;
+                        continue;
+                    };
+                    if let Some(mut lives) = world.get_resource_mut::<Lives>() {
+                        lives.remaining += 1;
+                    }
+                    spawn_point.position = snapshot.translation;
+                    comebacks.insert(snapshot.id, (spawn_point, device));
+                }
+                SnapshotKind::Zombie { iid: Some(iid) } => {
+                    // Or the save would take it out again as soon as it spawns
+                    if let Some(mut zombie_record) = world.get_resource_mut::<ZombieRecord>() {
+                        zombie_record.killed.remove(iid);
+                    }
+                }
+                SnapshotKind::Buddy => missing += 1,
+                _ => {}
+            }
+        }
+
+        let level_world = world
+            .query_filtered::<Entity, With<Handle<LdtkAsset>>>()
+            .iter(world)
+            .next();
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, world);
+            let asset_server = world.resource::<AssetServer>();
+            // Bullets last, their shooter may be coming back too
+            let (bullets, others): (Vec<&EntitySnapshot>, Vec<&EntitySnapshot>) = gone
+                .into_iter()
+                .filter(|snapshot| !present.contains_key(&snapshot.id))
+                .partition(|snapshot| matches!(snapshot.kind, SnapshotKind::Bullet { .. }));
+            for snapshot in others.into_iter().chain(bullets) {
+                let origin = snapshot.translation;
+                let ahead = origin.truncate() + Vec2::X;
+                let entity = match &snapshot.kind {
+                    SnapshotKind::Player { slot } => {
+                        let Some((spawn_point, device)) = comebacks.get(&snapshot.id) else {
+                            continue;
+                        };
+                        println!("Player {} is back", slot + 1);
+                        spawn_player_at(&mut commands, world.resource::<CharacterSheet>(), spawn_point, PlayerId(*slot), *device)
+                    }
+                    SnapshotKind::Zombie { iid } => {
+                        let entity_instance = EntityInstance {
+                            identifier: "Zombie".to_string(),
+                            iid: iid.clone().unwrap_or_default(),
+                            ..default()
+                        };
+                        let zombie = commands.spawn(ZombieBundle {
+                            sprite_bundle: SpriteBundle {
+                                transform: Transform::from_translation(origin),
+                                texture: asset_server.load("zombie.png"),
+                                ..default()
+                            },
+                            collider_bundle: ColliderBundle::from(&entity_instance),
+                            worldly: Worldly::from_entity_info(&entity_instance),
+                            entity_instance,
+                            ..default()
+                        }).id();
+                        if let Some(level_world) = level_world {
+                            commands.entity(zombie).set_parent(level_world);
+                        }
+                        zombie
+                    }
+                    SnapshotKind::Bullet { shooter } => {
+                        let Some(shooter) = shooter.and_then(|shooter| present.get(&shooter).copied()) else {
+                            missing += 1;
+                            continue;
+                        };
+                        spawn_bullet(&mut commands, asset_server, shooter, origin, ahead)
+                    }
+                    SnapshotKind::Grenade => spawn_grenade(&mut commands, asset_server, origin, ahead),
+                    SnapshotKind::Buddy => continue,
+                };
+                commands.entity(entity).insert(RollbackId(snapshot.id));
+                present.insert(snapshot.id, entity);
+            }
+        }
+        queue.apply(world);
+        missing
+    }
+
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        match bincode::serialize(self) {
+            Ok(bytes) => Some(bytes),
+            Err(error) => {
+                println!("Could not encode snapshot {}: {}", self.tick, error);
+                None
+            }
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<WorldSnapshot> {
+        match bincode::deserialize(bytes) {
+            Ok(snapshot) => Some(snapshot),
+            Err(error) => {
+                println!("Could not decode snapshot: {}", error);
+                None
+            }
+        }
+    }
+}
+
+/// Gives every tracked entity that has none yet the next `RollbackId`
+fn assign_rollback_ids(world: &mut World) {
+    let unassigned: Vec<Entity> = world
+        .query_filtered::<Entity, (Without<RollbackId>, Or<(With<Player>, With<Zombie>, With<Buddy>, With<Bullet>, With<Grenade>)>)>()
+        .iter(world)
+        .collect();
+    for entity in unassigned {
+        let mut ids = world.resource_mut::<RollbackIds>();
+        let id = RollbackId(ids.next_id);
+        ids.next_id += 1;
+        world.entity_mut(entity).insert(id);
+    }
+}
+
+/// Device of the player of `slot` waiting to respawn, who stops waiting
+fn take_pending_respawn(world: &mut World, slot: usize) -> Option<InputDevice> {
+    let mut respawns = world.get_resource_mut::<Respawns>()?;
+    let index = respawns.pending.iter().position(|respawn| respawn.player_id.0 == slot)?;
+    Some(respawns.pending.remove(index).device)
+}
+
+/// Winds a timer to `elapsed` seconds, finished if that is all of it
+fn set_timer(timer: &mut Timer, elapsed: f32) {
+    timer.reset();
+    timer.set_elapsed(Duration::from_secs_f32(elapsed));
+    timer.tick(Duration::ZERO);
+}
+
+impl SnapshotBuffer {
+    /// Keeps `snapshot`, dropping the oldest one when full
+    pub fn push(&mut self, snapshot: &WorldSnapshot) {
+        let Some(bytes) = snapshot.to_bytes() else {
+            return;
+        };
+        while self.frames.len() >= self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back((snapshot.tick, bytes));
+    }
+
+    pub fn get(&self, tick: u32) -> Option<WorldSnapshot> {
+        self.frames
+            .iter()
+            .find(|(frame_tick, _)| *frame_tick == tick)
+            .and_then(|(_, bytes)| WorldSnapshot::from_bytes(bytes))
+    }
+
+    pub fn oldest_tick(&self) -> Option<u32> {
+        self.frames.front().map(|(tick, _)| *tick)
+    }
+
+    pub fn latest_tick(&self) -> Option<u32> {
+        self.frames.back().map(|(tick, _)| *tick)
+    }
+
+    /// Forgets the frames after `tick`, the game goes on from there differently
+    pub fn discard_after(&mut self, tick: u32) {
+        self.frames.retain(|(frame_tick, _)| *frame_tick <= tick);
+        self.next_tick = tick + 1;
+    }
+}
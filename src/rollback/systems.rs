@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::net::components::NetMode;
+use crate::replay::components::ReplayMode;
+use super::components::*;
+
+/// Takes a snapshot after every fixed step of gameplay
+pub fn record_snapshot(world: &mut World) {
+    let tick = world.resource::<SnapshotBuffer>().next_tick;
+    let snapshot = WorldSnapshot::capture(world, tick);
+    let mut buffer = world.resource_mut::<SnapshotBuffer>();
+    buffer.push(&snapshot);
+    buffer.next_tick += 1;
+}
+
+/// Snapshots of one game mean nothing in the next one or on another map
+pub fn clear_snapshots(
+    mut buffer: ResMut<SnapshotBuffer>,
+) {
+    *buffer = SnapshotBuffer::default();
+}
+
+/// Puts the game back `ticks` fixed steps, or as far as the buffer goes.
+/// Returns the tick it went back to, `None` with nothing recorded yet.
+pub fn rewind(world: &mut World, ticks: u32) -> Option<u32> {
+    let buffer = world.resource::<SnapshotBuffer>();
+    let tick = buffer.latest_tick()?.saturating_sub(ticks).max(buffer.oldest_tick()?);
+    let snapshot = buffer.get(tick)?;
+    let missing = snapshot.restore(world);
+    if missing > 0 {
+        println!("{} entities died since tick {} and could not come back", missing, tick);
+    }
+    world.resource_mut::<SnapshotBuffer>().discard_after(tick);
+    Some(tick)
+}
+
+/// A rewind would change the game under a recording or behind the backs of the other players
+pub fn can_rewind(
+    replay_mode: Res<ReplayMode>,
+    net_mode: Res<NetMode>,
+) -> bool {
+    *replay_mode == ReplayMode::Off && *net_mode == NetMode::Off
+}
+
+pub fn rewind_on_key(world: &mut World) {
+    if !world.resource::<Input<KeyCode>>().just_pressed(KeyCode::Back) {
+        return;
+    }
+    if let Some(tick) = rewind(world, REWIND_TICKS) {
+        println!("Rewound to tick {}", tick);
+    }
+}
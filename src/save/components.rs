@@ -0,0 +1,172 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{Ammo, FireRate, Health, Inventory, MaxHealth, ReloadTimer, Weapon};
+use crate::game::campaign::components::PlayerLoadout;
+use crate::game::objectives::components::Objective;
+use crate::storage::Timestamp;
+
+/// Bumped whenever `SaveGame` changes shape, saves of other versions are not loaded
+pub const SAVE_VERSION: u32 = 2;
+/// Range weapon timers of a save are kept in, saves can be edited by hand
+const MIN_TIMER_SECONDS: f32 = 0.01;
+const MAX_TIMER_SECONDS: f32 = 60.0;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SaveSlot {
+    /// Written every time the players enter a level
+    Auto,
+    /// Written from the pause menu
+    Quick,
+}
+
+impl SaveSlot {
+    pub const ALL: [SaveSlot; 2] = [SaveSlot::Auto, SaveSlot::Quick];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SaveSlot::Auto => "Autosave",
+            SaveSlot::Quick => "Quicksave",
+        }
+    }
+}
+
+/// A player as it was when the game got saved
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub slot: usize,
+    pub position: Vec2,
+    pub facing_left: bool,
+    pub health: i32,
+    pub max_health: i32,
+    pub bullets: u32,
+    pub mag_size: u32,
+    /// Seconds between shots
+    pub fire_rate: f32,
+    /// Seconds a reload takes
+    pub reload_time: f32,
+    pub grenades: u32,
+    pub keys: Vec<String>,
+}
+
+impl SavedPlayer {
+    pub fn new(slot: usize, position: Vec2, facing_left: bool, loadout: &PlayerLoadout) -> Self {
+        Self {
+            slot,
+            position,
+            facing_left,
+            health: loadout.health.health_points,
+            max_health: loadout.max_health.health_points,
+            bullets: loadout.weapon.ammo.bullets,
+            mag_size: loadout.weapon.mag_size,
+            fire_rate: loadout.weapon.fire_rate.timer.duration().as_secs_f32(),
+            reload_time: loadout.weapon.reload_timer.reload_timer.duration().as_secs_f32(),
+            grenades: loadout.inventory.grenades,
+            keys: loadout.inventory.keys.clone(),
+        }
+    }
+
+    /// What the player gets back, with its weapon as it was upgraded
+    pub fn loadout(&self) -> PlayerLoadout {
+        PlayerLoadout {
+            health: Health {
+                health_points: self.health,
+            },
+            max_health: MaxHealth {
+                health_points: self.max_health,
+            },
+            weapon: Weapon {
+                fire_rate: FireRate {
+                    timer: Timer::from_seconds(timer_seconds(self.fire_rate), TimerMode::Repeating),
+                },
+                ammo: Ammo {
+                    bullets: self.bullets,
+                },
+                mag_size: self.mag_size,
+                reload_timer: ReloadTimer {
+                    reload_timer: Timer::from_seconds(timer_seconds(self.reload_time), TimerMode::Once),
+                },
+                reloading: false,
+            },
+            inventory: Inventory {
+                grenades: self.grenades,
+                keys: self.keys.clone(),
+            },
+        }
+    }
+}
+
+/// `seconds` within the timer range, a negative or infinite one would panic
+fn timer_seconds(seconds: f32) -> f32 {
+    seconds.max(MIN_TIMER_SECONDS).min(MAX_TIMER_SECONDS)
+}
+
+/// A zombie still alive when the game got saved
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SavedZombie {
+    /// Iid of the zombie's LDtk entity
+    pub iid: String,
+    pub position: Vec2,
+    pub health: i32,
+}
+
+/// Everything needed to pick a run back up, written as RON to the saves folder
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub saved_at: Timestamp,
+    /// Path of the `.ldtk` file, as listed in the campaign manifest
+    pub world: String,
+    /// Index of `world` in the manifest, used if the manifest no longer lists the path
+    pub world_index: usize,
+    /// Identifier of the selected level
+    pub level: Option<String>,
+    pub lives: u32,
+    pub players: Vec<SavedPlayer>,
+    /// Objectives of every level visited, by level iid
+    pub objectives: HashMap<String, Vec<Objective>>,
+    pub killed_zombies: Vec<String>,
+    pub zombies: Vec<SavedZombie>,
+}
+
+/// Iids of the zombies killed this run, so they stay dead after a load
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ZombieRecord {
+    pub killed: HashSet<String>,
+}
+
+/// Zombies of a loaded game, put back in place as the world spawns them
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SavedZombies {
+    pub zombies: HashMap<String, SavedZombie>,
+}
+
+/// Save picked in the main menu, applied once the loading screen is up
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PendingLoad(pub Option<SaveGame>);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SaveRequested {
+    pub slot: SaveSlot,
+}
+
+/// Overlay of the main menu listing the saves that can be loaded
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum LoadMenuState {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(Component)]
+pub struct LoadMenu {}
+
+#[derive(Component)]
+pub struct LoadSlotButton {
+    pub slot: SaveSlot,
+}
+
+#[derive(Component)]
+pub struct LoadBackButton {}
@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::PathBuf;
+
+use ron::ser::PrettyConfig;
+
+use crate::storage::data_path;
+use super::components::{SaveGame, SaveSlot, SAVE_VERSION};
+
+pub const SAVE_DIR: &str = "saves";
+
+impl SaveSlot {
+    pub fn path(self) -> PathBuf {
+        let file = match self {
+            SaveSlot::Auto => "autosave.ron",
+            SaveSlot::Quick => "quicksave.ron",
+        };
+        data_path(SAVE_DIR).join(file)
+    }
+}
+
+impl SaveGame {
+    /// The save in `slot`, if there is one of this version
+    pub fn read(slot: SaveSlot) -> Option<SaveGame> {
+        let path = slot.path();
+        let text = fs::read_to_string(&path).ok()?;
+        let save: SaveGame = match ron::from_str(&text) {
+            Ok(save) => save,
+            Err(error) => {
+                println!("Could not read {}: {}", path.display(), error);
+                return None;
+            }
+        };
+        if save.version != SAVE_VERSION {
+            println!(
+                "{} is a version {} save, only version {} can be loaded",
+                path.display(),
+                save.version,
+                SAVE_VERSION
+            );
+            return None;
+        }
+        Some(save)
+    }
+
+    /// The most recently written save of any slot
+    pub fn newest() -> Option<SaveGame> {
+        SaveSlot::ALL
+            .into_iter()
+            .filter_map(SaveGame::read)
+            .max_by_key(|save| save.saved_at)
+    }
+
+    pub fn write(&self, slot: SaveSlot) {
+        let path = slot.path();
+        let text = match ron::ser::to_string_pretty(self, PrettyConfig::default()) {
+            Ok(text) => text,
+            Err(error) => {
+                println!("Could not write save: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = fs::create_dir_all(data_path(SAVE_DIR)).and_then(|_| fs::write(&path, text)) {
+            println!("Could not write {}: {}", path.display(), error);
+            return;
+        }
+        println!("Saved to {}", path.display());
+    }
+
+    pub fn label(&self, slot: SaveSlot) -> String {
+        let place = self.level.as_deref().unwrap_or(self.world.as_str());
+        format!("{}: {}", slot.label(), place)
+    }
+}
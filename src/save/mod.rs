@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+pub mod components;
+pub mod file;
+mod systems;
+
+use crate::{AppState, HostGameplaySet};
+
+use self::components::{LoadMenuState, PendingLoad, SaveRequested, SavedZombies, ZombieRecord};
+use self::systems::{
+    layout::{spawn_load_menu, despawn_load_menu},
+    interactions::{interact_with_load_slot_buttons, interact_with_load_back_button, escape_load_menu, close_load_menu},
+    loading::{reset_save_records, apply_pending_load, restore_zombies},
+    saving::{record_killed_zombies, request_autosave, write_saves},
+};
+
+/// Saving the run to the saves folder and picking it back up from the main menu
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_state::<LoadMenuState>()
+        .add_event::<SaveRequested>()
+        .init_resource::<PendingLoad>()
+        .init_resource::<ZombieRecord>()
+        .init_resource::<SavedZombies>()
+        .add_system(reset_save_records.in_schedule(OnExit(AppState::MainMenu)))
+        .add_system(reset_save_records.in_schedule(OnExit(AppState::GameOver)))
+        .add_system(apply_pending_load.in_schedule(OnEnter(AppState::Loading)))
+        .add_system(restore_zombies)
+        .add_systems(
+            (
+                record_killed_zombies,
+                request_autosave,
+                write_saves.after(request_autosave),
+            )
+            .in_set(HostGameplaySet)
+            .in_set(OnUpdate(AppState::Game))
+        )
+        //OnEnter State Systems
+        .add_system(spawn_load_menu.in_schedule(OnEnter(LoadMenuState::Open)))
+        //Systems
+        .add_systems(
+            (
+                interact_with_load_slot_buttons,
+                interact_with_load_back_button,
+                escape_load_menu,
+            ).in_set(OnUpdate(LoadMenuState::Open))
+        )
+        .add_system(close_load_menu.in_schedule(OnExit(AppState::MainMenu)))
+        //OnExit State Systems
+        .add_system(despawn_load_menu.in_schedule(OnExit(LoadMenuState::Open)));
+    }
+}
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::main_menu::styles::*;
+use crate::save::components::*;
+use crate::systems::ButtonInteraction;
+
+pub fn interact_with_load_slot_buttons(
+    mut button_query: Query<
+        (&Interaction, &LoadSlotButton, &mut BackgroundColor),
+        Changed<Interaction>
+    >,
+    mut pending_load: ResMut<PendingLoad>,
+    mut load_menu_state_next_state: ResMut<NextState<LoadMenuState>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>
+) {
+    for (interaction, button, mut background_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                // Read again, the file may have changed since the menu opened
+                let Some(save) = SaveGame::read(button.slot) else {
+                    continue;
+                };
+                pending_load.0 = Some(save);
+                load_menu_state_next_state.set(LoadMenuState::Closed);
+                app_state_next_state.set(AppState::Loading);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_load_back_button(
+    mut button_query: ButtonInteraction<LoadBackButton>,
+    mut load_menu_state_next_state: ResMut<NextState<LoadMenuState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                load_menu_state_next_state.set(LoadMenuState::Closed);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn escape_load_menu(
+    keyboard: Res<Input<KeyCode>>,
+    mut load_menu_state_next_state: ResMut<NextState<LoadMenuState>>
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        load_menu_state_next_state.set(LoadMenuState::Closed);
+    }
+}
+
+/// The main menu went away, with or without a save picked
+pub fn close_load_menu(
+    mut load_menu_state_next_state: ResMut<NextState<LoadMenuState>>
+) {
+    load_menu_state_next_state.set(LoadMenuState::Closed);
+}
@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::main_menu::styles::*;
+use crate::save::components::*;
+
+pub fn spawn_load_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    build_load_menu(&mut commands, &asset_server);
+}
+
+pub fn despawn_load_menu(
+    mut commands: Commands,
+    load_menu_query: Query<Entity, With<LoadMenu>>
+) {
+    if let Ok(load_menu_entity) = load_menu_query.get_single() {
+        commands.entity(load_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn build_load_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
+    let saves: Vec<(SaveSlot, SaveGame)> = SaveSlot::ALL
+        .into_iter()
+        .filter_map(|slot| SaveGame::read(slot).map(|save| (slot, save)))
+        .collect();
+
+    let load_menu_entity = commands.spawn(
+        (
+            NodeBundle {
+                style: SETTINGS_MENU_STYLE,
+                background_color: SETTINGS_BACKGROUND_COLOR.into(),
+                // Drawn over the main menu and keeps clicks away from it
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            LoadMenu{},
+        )
+    )
+    .with_children(|parent| {
+        //title
+        parent.spawn(
+            NodeBundle {
+                style: TITLE_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            parent.spawn(
+                TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Load Game",
+                                get_title_text_style(asset_server)
+                            )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //saves
+        if saves.is_empty() {
+            parent.spawn(
+                TextBundle::from_section(
+                    "No saved games",
+                    get_setting_text_style(asset_server),
+                )
+            );
+        }
+        for (slot, save) in saves.iter() {
+            parent.spawn(
+                (
+                    ButtonBundle {
+                        style: SETTING_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    LoadSlotButton { slot: *slot }
+                )
+            )
+            .with_children(|parent|{
+                parent.spawn(
+                    TextBundle::from_section(
+                        save.label(*slot),
+                        get_setting_text_style(asset_server),
+                    )
+                );
+            });
+        }
+        //back button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: NORMAL_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                LoadBackButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Back",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+    })
+    .id();
+
+    load_menu_entity
+}
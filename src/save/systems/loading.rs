@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::components::Health;
+use crate::game::campaign::components::{CampaignProgress, CarryOver};
+use crate::game::campaign::manifest::CampaignManifest;
+use crate::game::enemies::components::Zombie;
+use crate::game::objectives::components::MissionTracker;
+use crate::game::player::components::{Loadout, PlayerId};
+use crate::game::player::resources::{Lives, RestoredSpawns, SpawnPoint};
+use crate::save::components::*;
+
+/// A new run starts without anything killed or restored
+pub fn reset_save_records(
+    mut zombie_record: ResMut<ZombieRecord>,
+    mut saved_zombies: ResMut<SavedZombies>,
+) {
+    zombie_record.killed.clear();
+    saved_zombies.zombies.clear();
+}
+
+/// Points the campaign at the saved world and level and hands the rest to the systems
+/// that set up the players, objectives and zombies once they spawn
+#[allow(clippy::too_many_arguments)]
+pub fn apply_pending_load(
+    mut pending_load: ResMut<PendingLoad>,
+    mut progress: ResMut<CampaignProgress>,
+    manifests: Res<Assets<CampaignManifest>>,
+    mut carry_over: ResMut<CarryOver>,
+    mut restored_spawns: ResMut<RestoredSpawns>,
+    mut tracker: ResMut<MissionTracker>,
+    mut lives: ResMut<Lives>,
+    mut zombie_record: ResMut<ZombieRecord>,
+    mut saved_zombies: ResMut<SavedZombies>,
+) {
+    let Some(save) = pending_load.0.take() else {
+        return;
+    };
+
+    println!("Loading saved game");
+    // The manifest may have been reordered since, the path is what counts
+    progress.world = manifests
+        .get(&progress.manifest)
+        .and_then(|manifest| manifest.worlds.iter().position(|world| *world == save.world))
+        .unwrap_or(save.world_index);
+    progress.level = save.level;
+    lives.remaining = save.lives;
+    tracker.levels = save.objectives;
+    tracker.current = None;
+    zombie_record.killed = save.killed_zombies.into_iter().collect();
+    saved_zombies.zombies = save
+        .zombies
+        .into_iter()
+        .map(|zombie| (zombie.iid.clone(), zombie))
+        .collect();
+
+    for player in save.players {
+        let player_id = PlayerId(player.slot);
+        restored_spawns.points.insert(player_id, SpawnPoint {
+            position: player.position.extend(0.0),
+            facing_left: player.facing_left,
+            loadout: Loadout::default(),
+        });
+        carry_over.players.insert(player_id, player.loadout());
+    }
+}
+
+/// Zombies killed this run are removed as soon as the world spawns them again,
+/// the ones of a loaded game go back to where they were
+pub fn restore_zombies(
+    mut commands: Commands,
+    zombie_record: Res<ZombieRecord>,
+    mut saved_zombies: ResMut<SavedZombies>,
+    mut zombie_query: Query<(Entity, &EntityInstance, &mut Transform, &mut Health), Added<Zombie>>,
+) {
+    for (zombie, entity_instance, mut transform, mut health) in zombie_query.iter_mut() {
+        if zombie_record.killed.contains(&entity_instance.iid) {
+            commands.entity(zombie).despawn();
+            continue;
+        }
+        let Some(saved) = saved_zombies.zombies.remove(&entity_instance.iid) else {
+            continue;
+        };
+        transform.translation = saved.position.extend(transform.translation.z);
+        health.health_points = saved.health;
+    }
+}
@@ -0,0 +1,4 @@
+pub mod interactions;
+pub mod layout;
+pub mod loading;
+pub mod saving;
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::components::{Health, Inventory, MaxHealth, Weapon};
+use crate::events::LevelEntered;
+use crate::game::campaign::components::{CampaignProgress, PlayerLoadout};
+use crate::game::campaign::manifest::CampaignManifest;
+use crate::game::enemies::components::{Zombie, ZombieKilled};
+use crate::game::objectives::components::MissionTracker;
+use crate::game::player::components::{Player, PlayerId};
+use crate::game::player::resources::Lives;
+use crate::save::components::*;
+use crate::storage::now;
+
+pub fn record_killed_zombies(
+    mut killed_events: EventReader<ZombieKilled>,
+    mut zombie_record: ResMut<ZombieRecord>,
+) {
+    for event in killed_events.iter() {
+        if let Some(iid) = &event.iid {
+            zombie_record.killed.insert(iid.clone());
+        }
+    }
+}
+
+/// Every level the players walk into is saved to the autosave slot
+pub fn request_autosave(
+    mut entered_events: EventReader<LevelEntered>,
+    mut save_events: EventWriter<SaveRequested>,
+) {
+    if entered_events.iter().count() > 0 {
+        save_events.send(SaveRequested { slot: SaveSlot::Auto });
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn write_saves(
+    mut save_events: EventReader<SaveRequested>,
+    progress: Res<CampaignProgress>,
+    manifests: Res<Assets<CampaignManifest>>,
+    level_selection: Res<LevelSelection>,
+    world_query: Query<&Handle<LdtkAsset>>,
+    ldtk_assets: Res<Assets<LdtkAsset>>,
+    tracker: Res<MissionTracker>,
+    lives: Res<Lives>,
+    zombie_record: Res<ZombieRecord>,
+    player_query: Query<
+        (&PlayerId, &Transform, &TextureAtlasSprite, &Health, &MaxHealth, &Weapon, &Inventory),
+        With<Player>
+    >,
+    zombie_query: Query<(&EntityInstance, &Transform, &Health), With<Zombie>>,
+) {
+    let slots: Vec<SaveSlot> = save_events.iter().map(|event| event.slot).collect();
+    if slots.is_empty() {
+        return;
+    }
+    let Some(world) = manifests
+        .get(&progress.manifest)
+        .and_then(|manifest| manifest.worlds.get(progress.world)) else {
+        println!("There is no campaign world to save");
+        return;
+    };
+
+    let level = world_query
+        .iter()
+        .filter_map(|world_handle| ldtk_assets.get(world_handle))
+        .flat_map(|ldtk_asset| ldtk_asset.project.levels.iter().enumerate())
+        .find(|(index, level)| level_selection.is_match(index, level))
+        .map(|(_, level)| level.identifier.clone());
+
+    let save = SaveGame {
+        version: SAVE_VERSION,
+        saved_at: now(),
+        world: world.clone(),
+        world_index: progress.world,
+        level,
+        lives: lives.remaining,
+        players: player_query
+            .iter()
+            .map(|(player_id, transform, sprite, health, max_health, weapon, inventory)| {
+                let loadout = PlayerLoadout {
+                    health: health.clone(),
+                    max_health: max_health.clone(),
+                    weapon: weapon.clone(),
+                    inventory: inventory.clone(),
+                };
+                SavedPlayer::new(player_id.0, transform.translation.truncate(), sprite.flip_x, &loadout)
+            })
+            .collect(),
+        objectives: tracker.levels.clone(),
+        killed_zombies: zombie_record.killed.iter().cloned().collect(),
+        zombies: zombie_query
+            .iter()
+            .map(|(entity_instance, transform, health)| SavedZombie {
+                iid: entity_instance.iid.clone(),
+                position: transform.translation.truncate(),
+                health: health.health_points,
+            })
+            .collect(),
+    };
+    for slot in slots {
+        save.write(slot);
+    }
+}
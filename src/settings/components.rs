@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+use serde::{Deserialize, Serialize};
+
+/// Window sizes the settings screen cycles through
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+/// Camera look-ahead strengths, as the share of the aim distance the camera moves towards
+pub const LOOK_AHEAD_STEPS: [f32; 4] = [0.0, 0.1, 0.2, 0.3];
+/// Volumes go up in tenths and wrap around to silent
+const VOLUME_STEPS: f32 = 10.0;
+
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum SettingsState {
+    #[default]
+    Closed,
+    /// Settings screen shown on top of the main menu or the pause menu
+    Open,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum WindowModeSetting {
+    #[default]
+    Windowed,
+    BorderlessFullscreen,
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::BorderlessFullscreen => WindowMode::BorderlessFullscreen,
+            WindowModeSetting::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+
+    fn next(&self) -> WindowModeSetting {
+        match self {
+            WindowModeSetting::Windowed => WindowModeSetting::BorderlessFullscreen,
+            WindowModeSetting::BorderlessFullscreen => WindowModeSetting::Fullscreen,
+            WindowModeSetting::Fullscreen => WindowModeSetting::Windowed,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::BorderlessFullscreen => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }
+    }
+}
+
+/// Keyboard actions that can be rebound, firing and aiming stay on the mouse
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum KeyAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Reload,
+    ThrowGrenade,
+    Talk,
+    Interact,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 8] = [
+        KeyAction::MoveUp,
+        KeyAction::MoveDown,
+        KeyAction::MoveLeft,
+        KeyAction::MoveRight,
+        KeyAction::Reload,
+        KeyAction::ThrowGrenade,
+        KeyAction::Talk,
+        KeyAction::Interact,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            KeyAction::MoveUp => "Move up",
+            KeyAction::MoveDown => "Move down",
+            KeyAction::MoveLeft => "Move left",
+            KeyAction::MoveRight => "Move right",
+            KeyAction::Reload => "Reload",
+            KeyAction::ThrowGrenade => "Grenade",
+            KeyAction::Talk => "Talk",
+            KeyAction::Interact => "Interact",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub move_up: KeyCode,
+    pub move_down: KeyCode,
+    pub move_left: KeyCode,
+    pub move_right: KeyCode,
+    pub reload: KeyCode,
+    pub throw_grenade: KeyCode,
+    pub talk: KeyCode,
+    pub interact: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            move_up: KeyCode::W,
+            move_down: KeyCode::S,
+            move_left: KeyCode::A,
+            move_right: KeyCode::D,
+            reload: KeyCode::R,
+            throw_grenade: KeyCode::G,
+            talk: KeyCode::T,
+            interact: KeyCode::E,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: KeyAction) -> KeyCode {
+        match action {
+            KeyAction::MoveUp => self.move_up,
+            KeyAction::MoveDown => self.move_down,
+            KeyAction::MoveLeft => self.move_left,
+            KeyAction::MoveRight => self.move_right,
+            KeyAction::Reload => self.reload,
+            KeyAction::ThrowGrenade => self.throw_grenade,
+            KeyAction::Talk => self.talk,
+            KeyAction::Interact => self.interact,
+        }
+    }
+
+    pub fn set(&mut self, action: KeyAction, key: KeyCode) {
+        let binding = match action {
+            KeyAction::MoveUp => &mut self.move_up,
+            KeyAction::MoveDown => &mut self.move_down,
+            KeyAction::MoveLeft => &mut self.move_left,
+            KeyAction::MoveRight => &mut self.move_right,
+            KeyAction::Reload => &mut self.reload,
+            KeyAction::ThrowGrenade => &mut self.throw_grenade,
+            KeyAction::Talk => &mut self.talk,
+            KeyAction::Interact => &mut self.interact,
+        };
+        *binding = key;
+    }
+}
+
+/// Player settings, read from `SETTINGS_FILE` at startup and written back when the settings screen closes
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub window_mode: WindowModeSetting,
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    /// Volumes go from 0.0 to 1.0
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub camera_look_ahead: f32,
+    pub screen_shake: bool,
+    pub key_bindings: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowModeSetting::Windowed,
+            resolution: RESOLUTIONS[0],
+            vsync: true,
+            master_volume: 1.0,
+            music_volume: 0.7,
+            sfx_volume: 0.8,
+            camera_look_ahead: 0.2,
+            screen_shake: true,
+            key_bindings: KeyBindings::default(),
+        }
+    }
+}
+
+fn next_volume(volume: f32) -> f32 {
+    let step = (volume * VOLUME_STEPS).round() + 1.0;
+    if step > VOLUME_STEPS {
+        0.0
+    } else {
+        step / VOLUME_STEPS
+    }
+}
+
+fn next_step<T: Copy + PartialEq>(steps: &[T], current: T) -> T {
+    let index = steps.iter().position(|step| *step == current).map_or(0, |index| index + 1);
+    steps[index % steps.len()]
+}
+
+impl Settings {
+    /// Moves `kind` on to its next value, key bindings are changed by pressing a key instead
+    pub fn cycle(&mut self, kind: SettingKind) {
+        match kind {
+            SettingKind::WindowMode => self.window_mode = self.window_mode.next(),
+            SettingKind::Resolution => self.resolution = next_step(&RESOLUTIONS, self.resolution),
+            SettingKind::VSync => self.vsync = !self.vsync,
+            SettingKind::MasterVolume => self.master_volume = next_volume(self.master_volume),
+            SettingKind::MusicVolume => self.music_volume = next_volume(self.music_volume),
+            SettingKind::SfxVolume => self.sfx_volume = next_volume(self.sfx_volume),
+            SettingKind::CameraLookAhead => self.camera_look_ahead = next_step(&LOOK_AHEAD_STEPS, self.camera_look_ahead),
+            SettingKind::ScreenShake => self.screen_shake = !self.screen_shake,
+            SettingKind::KeyBinding(_) => {}
+        }
+    }
+
+    pub fn label(&self, kind: SettingKind) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" };
+        match kind {
+            SettingKind::WindowMode => format!("Window: {}", self.window_mode.label()),
+            SettingKind::Resolution => format!("Resolution: {}x{}", self.resolution.0, self.resolution.1),
+            SettingKind::VSync => format!("VSync: {}", on_off(self.vsync)),
+            SettingKind::MasterVolume => format!("Master volume: {:.0}%", self.master_volume * 100.0),
+            SettingKind::MusicVolume => format!("Music volume: {:.0}%", self.music_volume * 100.0),
+            SettingKind::SfxVolume => format!("SFX volume: {:.0}%", self.sfx_volume * 100.0),
+            SettingKind::CameraLookAhead => format!("Look-ahead: {:.0}%", self.camera_look_ahead * 100.0),
+            SettingKind::ScreenShake => format!("Screen shake: {}", on_off(self.screen_shake)),
+            SettingKind::KeyBinding(action) => format!("{}: {:?}", action.label(), self.key_bindings.get(action)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SettingKind {
+    WindowMode,
+    Resolution,
+    VSync,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    CameraLookAhead,
+    ScreenShake,
+    KeyBinding(KeyAction),
+}
+
+impl SettingKind {
+    /// Left column of the settings screen, key bindings go on the right
+    pub const GENERAL: [SettingKind; 8] = [
+        SettingKind::WindowMode,
+        SettingKind::Resolution,
+        SettingKind::VSync,
+        SettingKind::MasterVolume,
+        SettingKind::MusicVolume,
+        SettingKind::SfxVolume,
+        SettingKind::CameraLookAhead,
+        SettingKind::ScreenShake,
+    ];
+}
+
+/// Key binding waiting for the next key press
+#[derive(Resource, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Rebinding {
+    pub action: Option<KeyAction>,
+}
+
+#[derive(Component)]
+pub struct SettingsMenu {}
+
+#[derive(Component)]
+pub struct SettingButton {
+    pub kind: SettingKind,
+}
+
+#[derive(Component)]
+pub struct SettingText {
+    pub kind: SettingKind,
+}
+
+#[derive(Component)]
+pub struct BackButton {}
@@ -0,0 +1,57 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy::window::{PresentMode, WindowResolution};
+use ron::ser::PrettyConfig;
+
+use crate::storage::data_path;
+use super::components::Settings;
+
+pub const SETTINGS_FILE: &str = "settings.ron";
+
+impl Settings {
+    /// Settings saved last time, or the defaults if there are none yet
+    pub fn load() -> Settings {
+        let Ok(text) = fs::read_to_string(data_path(SETTINGS_FILE)) else {
+            return Settings::default();
+        };
+        match ron::from_str(&text) {
+            Ok(settings) => settings,
+            Err(error) => {
+                println!("Could not read {}: {}", SETTINGS_FILE, error);
+                Settings::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let text = match ron::ser::to_string_pretty(self, PrettyConfig::default()) {
+            Ok(text) => text,
+            Err(error) => {
+                println!("Could not write settings: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = fs::write(data_path(SETTINGS_FILE), text) {
+            println!("Could not write {}: {}", SETTINGS_FILE, error);
+        }
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    /// Window the game opens with
+    pub fn primary_window(&self) -> Window {
+        Window {
+            mode: self.window_mode.window_mode(),
+            resolution: WindowResolution::new(self.resolution.0 as f32, self.resolution.1 as f32),
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+pub mod components;
+pub mod file;
+mod systems;
+
+use crate::{AppState, SimulationState};
+
+use self::components::{Rebinding, SettingsState};
+use self::systems::{
+    layout::{spawn_settings_menu, despawn_settings_menu},
+    interactions::{interact_with_setting_buttons, escape_settings, capture_rebinding, update_setting_texts, interact_with_back_button, close_settings, save_settings},
+    window::apply_window_settings,
+};
+
+/// The `Settings` resource itself is loaded and inserted in `main`, before the window opens
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_state::<SettingsState>()
+        .init_resource::<Rebinding>()
+        .add_system(apply_window_settings)
+        //OnEnter State Systems
+        .add_system(spawn_settings_menu.in_schedule(OnEnter(SettingsState::Open)))
+        //Systems
+        .add_systems(
+            (
+                interact_with_setting_buttons,
+                escape_settings.before(capture_rebinding),
+                capture_rebinding.before(interact_with_setting_buttons),
+                update_setting_texts.after(interact_with_setting_buttons).after(capture_rebinding),
+                interact_with_back_button
+            ).in_set(OnUpdate(SettingsState::Open))
+        )
+        .add_system(close_settings.in_schedule(OnExit(AppState::MainMenu)))
+        .add_system(close_settings.in_schedule(OnExit(SimulationState::Paused)))
+        //OnExit State Systems
+        .add_systems(
+            (
+                despawn_settings_menu,
+                save_settings,
+            ).in_schedule(OnExit(SettingsState::Open))
+        );
+    }
+}
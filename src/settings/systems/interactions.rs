@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+use crate::main_menu::styles::*;
+use crate::settings::components::*;
+use crate::systems::ButtonInteraction;
+
+pub fn interact_with_setting_buttons(
+    mut settings: ResMut<Settings>,
+    mut rebinding: ResMut<Rebinding>,
+    mut button_query: Query<
+        (&Interaction, &SettingButton, &mut BackgroundColor),
+        Changed<Interaction>
+    >,
+) {
+    for (interaction, button, mut background_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                match button.kind {
+                    SettingKind::KeyBinding(action) => rebinding.action = Some(action),
+                    kind => settings.cycle(kind),
+                }
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+/// Escape leaves the settings screen, unless a key binding waits for its key
+pub fn escape_settings(
+    keyboard: Res<Input<KeyCode>>,
+    rebinding: Res<Rebinding>,
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>
+) {
+    if rebinding.action.is_none() && keyboard.just_pressed(KeyCode::Escape) {
+        settings_state_next_state.set(SettingsState::Closed);
+    }
+}
+
+/// The next key pressed after clicking a binding becomes its key, escape keeps the old one
+pub fn capture_rebinding(
+    keyboard: Res<Input<KeyCode>>,
+    mut settings: ResMut<Settings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    let Some(action) = rebinding.action else {
+        return;
+    };
+    let Some(key) = keyboard.get_just_pressed().next().copied() else {
+        return;
+    };
+    if key != KeyCode::Escape {
+        settings.key_bindings.set(action, key);
+    }
+    rebinding.action = None;
+}
+
+pub fn update_setting_texts(
+    settings: Res<Settings>,
+    rebinding: Res<Rebinding>,
+    mut text_query: Query<(&SettingText, &mut Text)>,
+) {
+    if !settings.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+    for (setting_text, mut text) in text_query.iter_mut() {
+        text.sections[0].value = match setting_text.kind {
+            SettingKind::KeyBinding(action) if rebinding.action == Some(action) => "Press a key...".to_string(),
+            kind => settings.label(kind),
+        };
+    }
+}
+
+pub fn interact_with_back_button(
+    mut button_query: ButtonInteraction<BackButton>,
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut(){
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                settings_state_next_state.set(SettingsState::Closed);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+/// The menu the settings were opened from went away
+pub fn close_settings(
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>
+) {
+    settings_state_next_state.set(SettingsState::Closed);
+}
+
+pub fn save_settings(
+    settings: Res<Settings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    rebinding.action = None;
+    settings.save();
+}
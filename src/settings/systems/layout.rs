@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::main_menu::styles::*;
+use crate::settings::components::*;
+
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+) {
+    build_settings_menu(&mut commands, &asset_server, &settings);
+}
+
+pub fn despawn_settings_menu(
+    mut commands: Commands,
+    settings_menu_query: Query<Entity, With<SettingsMenu>>
+) {
+    if let Ok(settings_menu_entity) = settings_menu_query.get_single() {
+        commands.entity(settings_menu_entity).despawn_recursive();
+    }
+}
+
+fn build_setting_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    settings: &Settings,
+    kind: SettingKind,
+) {
+    parent.spawn(
+        (
+            ButtonBundle {
+                style: SETTING_BUTTON_STYLE,
+                background_color: NORMAL_BUTTON_COLOR.into(),
+                ..default()
+            },
+            SettingButton { kind }
+        )
+    )
+    .with_children(|parent|{
+        parent.spawn((
+            TextBundle::from_section(
+                settings.label(kind),
+                get_setting_text_style(asset_server),
+            ),
+            SettingText { kind },
+        ));
+    });
+}
+
+pub fn build_settings_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    settings: &Settings,
+) -> Entity {
+    let settings_menu_entity = commands.spawn(
+        (
+            NodeBundle {
+                style: SETTINGS_MENU_STYLE,
+                background_color: SETTINGS_BACKGROUND_COLOR.into(),
+                // Drawn over the menu it was opened from and keeps clicks away from it
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            SettingsMenu{},
+        )
+    )
+    .with_children(|parent| {
+        //title
+        parent.spawn(
+            NodeBundle {
+                style: TITLE_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            parent.spawn(
+                TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Settings",
+                                get_title_text_style(asset_server)
+                            )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+        //settings, general ones left and key bindings right
+        parent.spawn(
+            NodeBundle {
+                style: SETTINGS_COLUMNS_STYLE,
+                ..default()
+            }
+        ).with_children(|parent|{
+            parent.spawn(
+                NodeBundle {
+                    style: SETTINGS_COLUMN_STYLE,
+                    ..default()
+                }
+            ).with_children(|parent|{
+                for kind in SettingKind::GENERAL {
+                    build_setting_button(parent, asset_server, settings, kind);
+                }
+            });
+            parent.spawn(
+                NodeBundle {
+                    style: SETTINGS_COLUMN_STYLE,
+                    ..default()
+                }
+            ).with_children(|parent|{
+                for action in KeyAction::ALL {
+                    build_setting_button(parent, asset_server, settings, SettingKind::KeyBinding(action));
+                }
+            });
+        });
+        //back button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: NORMAL_BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                BackButton{}
+            )
+        )
+        .with_children(|parent|{
+            parent.spawn(
+                TextBundle{
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(
+                                "Back",
+                                get_button_text_style(asset_server)
+                                )
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }
+            );
+        });
+    })
+    .id();
+
+    settings_menu_entity
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowMode};
+
+use crate::settings::components::Settings;
+
+pub fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+    let mode = settings.window_mode.window_mode();
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    // Fullscreen windows take the size of the monitor
+    let (width, height) = (settings.resolution.0 as f32, settings.resolution.1 as f32);
+    if mode == WindowMode::Windowed && (window.resolution.width() != width || window.resolution.height() != height) {
+        window.resolution.set(width, height);
+    }
+    let present_mode = settings.present_mode();
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
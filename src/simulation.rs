@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use bevy_rapier2d::prelude::*;
+
+use crate::{AppState, FixedGameplaySet, SimulationState, FIXED_TIMESTEP};
+use crate::events::{BulletHit, GrenadeExploded};
+use crate::resources::ScreenShake;
+use crate::systems::*;
+
+/// Physics and the gameplay stepped along with it, shared by the game and the headless app
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App){
+        app
+        // Physics steps in the fixed schedule together with gameplay, so it runs after
+        // everything bevy_ecs_ldtk spawned in the frame before is in place
+        .add_plugin(
+            RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0)
+                .with_default_system_setup(false)
+        )
+        .insert_resource(FixedTime::new_from_secs(FIXED_TIMESTEP))
+        .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+            schedule
+                .configure_sets(
+                    (
+                        PhysicsSet::SyncBackend,
+                        PhysicsSet::SyncBackendFlush,
+                        PhysicsSet::StepSimulation,
+                        PhysicsSet::Writeback,
+                    ).chain()
+                )
+                .configure_set(
+                    FixedGameplaySet
+                        .after(PhysicsSet::Writeback)
+                        .run_if(in_state(AppState::Game))
+                        .run_if(in_state(SimulationState::Running))
+                )
+                .add_systems(
+                    RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::SyncBackend)
+                        .in_base_set(PhysicsSet::SyncBackend)
+                )
+                .add_systems(
+                    RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::SyncBackendFlush)
+                        .in_base_set(PhysicsSet::SyncBackendFlush)
+                )
+                .add_systems(
+                    RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::StepSimulation)
+                        .in_base_set(PhysicsSet::StepSimulation)
+                )
+                .add_systems(
+                    RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::Writeback)
+                        .in_base_set(PhysicsSet::Writeback)
+                );
+        })
+        .insert_resource(RapierConfiguration {
+            gravity: Vec2::new(0.0, 0.0),
+            timestep_mode: TimestepMode::Fixed {
+                dt: FIXED_TIMESTEP,
+                substeps: 1,
+            },
+            ..Default::default()
+        })
+        .init_resource::<ScreenShake>()
+        .add_event::<BulletHit>()
+        .add_event::<GrenadeExploded>()
+        .add_systems(
+            (
+                bullet_collisions,
+                blow_up_granade,
+            )
+            .in_set(FixedGameplaySet)
+            .in_schedule(CoreSchedule::FixedUpdate)
+        )
+        // Bullets that left every loaded level won't hit anything anymore
+        .add_system(despawn_stray_bullets)
+        .add_system(add_interpolation)
+        .add_system(store_simulated_transforms.after(FixedGameplaySet).in_schedule(CoreSchedule::FixedUpdate))
+        .add_systems(
+            (
+                interpolate_transforms,
+                copy_interpolated_sprites,
+                despawn_orphaned_sprites,
+            )
+            .in_base_set(CoreSet::PostUpdate)
+            .before(TransformSystem::TransformPropagate)
+        );
+    }
+}
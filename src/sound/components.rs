@@ -0,0 +1,66 @@
+use bevy::audio::SpatialAudioSink;
+use bevy::prelude::*;
+
+#[derive(Resource, Clone, Debug)]
+pub struct SoundEffects {
+    pub gunshot: Handle<AudioSource>,
+    pub reload: Handle<AudioSource>,
+    pub grenade_explosion: Handle<AudioSource>,
+    pub zombie_groan: Handle<AudioSource>,
+    pub zombie_hurt: Handle<AudioSource>,
+    pub zombie_death: Handle<AudioSource>,
+    pub player_hurt: Handle<AudioSource>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MusicTrack {
+    Menu,
+    Game,
+    GameOver,
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct MusicTracks {
+    pub menu: Handle<AudioSource>,
+    pub game: Handle<AudioSource>,
+    pub game_over: Handle<AudioSource>,
+}
+
+impl MusicTracks {
+    pub fn get(&self, track: MusicTrack) -> Handle<AudioSource> {
+        match track {
+            MusicTrack::Menu => self.menu.clone(),
+            MusicTrack::Game => self.game.clone(),
+            MusicTrack::GameOver => self.game_over.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PlayingTrack {
+    pub track: MusicTrack,
+    pub sink: Handle<AudioSink>,
+    /// How far the track has faded in, from 0 to 1
+    pub fade: f32,
+}
+
+/// Sound effect playing from where it happened
+#[derive(Clone, Debug)]
+pub struct PlayingSound {
+    pub sink: Handle<SpatialAudioSink>,
+    pub position: Vec2,
+    /// Seconds since it started
+    pub age: f32,
+}
+
+/// Sound effects that are playing, they are heard by the player nearest to them
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PlayingSounds {
+    pub sounds: Vec<PlayingSound>,
+}
+
+/// Music that is playing. The last track fades in, all others fade out and stop.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Music {
+    pub playing: Vec<PlayingTrack>,
+}
@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+mod systems;
+pub mod components;
+
+use components::{Music, PlayingSounds};
+use systems::*;
+
+use crate::{AppState, SimulationState};
+
+/// Sound effects for gameplay events, heard from where they happen, and music following `AppState`
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Music>()
+            .init_resource::<PlayingSounds>()
+            .add_startup_system(load_sounds)
+            .add_systems(
+                (
+                    switch_music,
+                    fade_music.after(switch_music),
+                    play_sound_effects,
+                    move_sound_listeners,
+                )
+            )
+            .add_system(
+                zombie_groans
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(SimulationState::Running))
+            );
+    }
+}
@@ -0,0 +1,266 @@
+use bevy::audio::{AudioSinkPlayback, SpatialAudioSink};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+
+use super::components::*;
+use crate::AppState;
+use crate::events::{BulletHit, GrenadeExploded};
+use crate::game::enemies::components::{Zombie, ZombieKilled};
+use crate::game::player::components::{Player, PlayerHurt, PlayerReloaded, ShotFired};
+use crate::settings::components::Settings;
+
+/// Sounds further away from every player than this can't be heard
+const HEARING_DISTANCE: f32 = 300.0;
+/// Pixels are scaled down for spatial audio, which starts to fade sounds out past one unit away
+const SOUND_SCALE: f32 = 1.0 / 32.0;
+/// Distance between the ears of a listener, in scaled units
+const EAR_GAP: f32 = 2.0;
+/// A sound the audio output hasn't picked up after this many seconds never will be
+const SOUND_PICKUP_SECONDS: f32 = 1.0;
+const CROSSFADE_SECONDS: f32 = 1.5;
+/// Seconds between two groans of the zombies chasing someone
+const GROAN_INTERVAL: f32 = 2.5;
+
+pub fn load_sounds(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(SoundEffects {
+        gunshot: asset_server.load("audio/sfx/gunshot.wav"),
+        reload: asset_server.load("audio/sfx/reload.wav"),
+        grenade_explosion: asset_server.load("audio/sfx/grenade_explosion.wav"),
+        zombie_groan: asset_server.load("audio/sfx/zombie_groan.wav"),
+        zombie_hurt: asset_server.load("audio/sfx/zombie_hurt.wav"),
+        zombie_death: asset_server.load("audio/sfx/zombie_death.wav"),
+        player_hurt: asset_server.load("audio/sfx/player_hurt.wav"),
+    });
+    commands.insert_resource(MusicTracks {
+        menu: asset_server.load("audio/music/menu.wav"),
+        game: asset_server.load("audio/music/game.wav"),
+        game_over: asset_server.load("audio/music/game_over.wav"),
+    });
+}
+
+/// Player nearest to a sound at `position`, `None` when it is too far from all of them
+fn nearest_listener(position: Vec2, listeners: &[Vec2]) -> Option<Vec2> {
+    listeners
+        .iter()
+        .copied()
+        .filter(|listener| listener.distance(position) < HEARING_DISTANCE)
+        .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)))
+}
+
+fn spatial_position(position: Vec2) -> Vec3 {
+    (position * SOUND_SCALE).extend(0.0)
+}
+
+/// Plays `sound` from `position`, heard by the nearest player. Played as is when there are no players.
+fn play_at(
+    audio: &Audio,
+    audio_sinks: &Assets<SpatialAudioSink>,
+    playing: &mut PlayingSounds,
+    settings: &Settings,
+    sound: &Handle<AudioSource>,
+    position: Vec2,
+    listeners: &[Vec2],
+) {
+    let volume = settings.master_volume * settings.sfx_volume;
+    if volume <= 0.0 {
+        return;
+    }
+    let playback = PlaybackSettings::ONCE.with_volume(volume);
+    if listeners.is_empty() {
+        audio.play_with_settings(sound.clone(), playback);
+        return;
+    }
+    let Some(listener) = nearest_listener(position, listeners) else {
+        return;
+    };
+
+    let sink = audio.play_spatial_with_settings(
+        sound.clone(),
+        playback,
+        Transform::from_translation(spatial_position(listener)),
+        EAR_GAP,
+        spatial_position(position),
+    );
+    playing.sounds.push(PlayingSound {
+        sink: audio_sinks.get_handle(sink),
+        position,
+        age: 0.0,
+    });
+}
+
+/// Sounds keep coming from where they happened while the players move around
+pub fn move_sound_listeners(
+    time: Res<Time>,
+    audio_sinks: Res<Assets<SpatialAudioSink>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut playing: ResMut<PlayingSounds>,
+) {
+    let listeners: Vec<Vec2> = player_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+    playing.sounds.retain_mut(|sound| {
+        sound.age += time.delta_seconds();
+        // The sink only exists once the audio output picked the sound up
+        let Some(sink) = audio_sinks.get(&sound.sink) else {
+            return sound.age < SOUND_PICKUP_SECONDS;
+        };
+        if sink.empty() {
+            return false;
+        }
+        if let Some(listener) = nearest_listener(sound.position, &listeners) {
+            sink.set_listener_position(Transform::from_translation(spatial_position(listener)), EAR_GAP);
+        }
+        true
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn play_sound_effects(
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<SpatialAudioSink>>,
+    mut playing: ResMut<PlayingSounds>,
+    settings: Res<Settings>,
+    sounds: Res<SoundEffects>,
+    player_query: Query<&Transform, With<Player>>,
+    mut shot_events: EventReader<ShotFired>,
+    mut reload_events: EventReader<PlayerReloaded>,
+    mut exploded_events: EventReader<GrenadeExploded>,
+    mut hit_events: EventReader<BulletHit>,
+    mut killed_events: EventReader<ZombieKilled>,
+    mut hurt_events: EventReader<PlayerHurt>,
+) {
+    let listeners: Vec<Vec2> = player_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+    let mut play = |sound: &Handle<AudioSource>, position: Vec2| {
+        play_at(&audio, &audio_sinks, &mut playing, &settings, sound, position, &listeners);
+    };
+
+    for event in shot_events.iter() {
+        play(&sounds.gunshot, event.position);
+    }
+    for event in reload_events.iter() {
+        play(&sounds.reload, event.position);
+    }
+    for event in exploded_events.iter() {
+        play(&sounds.grenade_explosion, event.position);
+    }
+    for event in hit_events.iter() {
+        play(&sounds.zombie_hurt, event.position);
+    }
+    for event in killed_events.iter() {
+        play(&sounds.zombie_death, event.position);
+    }
+    for event in hurt_events.iter() {
+        play(&sounds.player_hurt, event.position);
+    }
+}
+
+/// Every so often one of the zombies on the move groans, taking turns
+#[allow(clippy::too_many_arguments)]
+pub fn zombie_groans(
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<SpatialAudioSink>>,
+    mut playing: ResMut<PlayingSounds>,
+    settings: Res<Settings>,
+    sounds: Res<SoundEffects>,
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    zombie_query: Query<(&Transform, &Velocity), With<Zombie>>,
+    mut groan_timer: Local<Option<Timer>>,
+    mut turn: Local<usize>,
+) {
+    let timer = groan_timer.get_or_insert_with(|| Timer::from_seconds(GROAN_INTERVAL, TimerMode::Repeating));
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let chasing: Vec<Vec2> = zombie_query
+        .iter()
+        .filter(|(_, velocity)| velocity.linvel != Vec2::ZERO)
+        .map(|(transform, _)| transform.translation.truncate())
+        .collect();
+    if chasing.is_empty() {
+        return;
+    }
+    *turn = (*turn + 1) % chasing.len();
+
+    let listeners: Vec<Vec2> = player_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+    play_at(&audio, &audio_sinks, &mut playing, &settings, &sounds.zombie_groan, chasing[*turn], &listeners);
+}
+
+fn state_track(state: AppState) -> Option<MusicTrack> {
+    match state {
+        AppState::MainMenu => Some(MusicTrack::Menu),
+        AppState::Game => Some(MusicTrack::Game),
+        AppState::GameOver => Some(MusicTrack::GameOver),
+        // Keep on with whatever plays between two maps
+        AppState::Loading => None,
+    }
+}
+
+pub fn switch_music(
+    app_state: Res<State<AppState>>,
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    tracks: Res<MusicTracks>,
+    mut music: ResMut<Music>,
+) {
+    if !app_state.is_changed() {
+        return;
+    }
+    let Some(track) = state_track(app_state.0) else {
+        return;
+    };
+    if music.playing.last().map_or(false, |playing| playing.track == track) {
+        return;
+    }
+
+    let sink = audio.play_with_settings(tracks.get(track), PlaybackSettings::LOOP.with_volume(0.0));
+    music.playing.push(PlayingTrack {
+        track,
+        sink: audio_sinks.get_handle(sink),
+        fade: 0.0,
+    });
+}
+
+/// Crossfades from the old tracks to the newest one, following the volume settings
+pub fn fade_music(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    mut music: ResMut<Music>,
+) {
+    let step = time.delta_seconds() / CROSSFADE_SECONDS;
+    let newest = music.playing.len().saturating_sub(1);
+    for (index, playing) in music.playing.iter_mut().enumerate() {
+        playing.fade = if index == newest {
+            (playing.fade + step).min(1.0)
+        } else {
+            (playing.fade - step).max(0.0)
+        };
+        // The sink only exists once the audio output picked the track up
+        if let Some(sink) = audio_sinks.get(&playing.sink) {
+            sink.set_volume(playing.fade * settings.master_volume * settings.music_volume);
+            if playing.fade == 0.0 && index != newest {
+                sink.stop();
+            }
+        }
+    }
+
+    // A faded out track is only dropped once it could be stopped
+    let mut index = 0;
+    music.playing.retain(|playing| {
+        let keep = index == newest || playing.fade > 0.0 || audio_sinks.get(&playing.sink).is_none();
+        index += 1;
+        keep
+    });
+}
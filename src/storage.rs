@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Where the game keeps the files it writes, and how it stamps them
+
+/// Seconds since the unix epoch
+pub type Timestamp = u64;
+
+/// Path of `file` next to the executable's working directory, where the assets folder is read from too
+pub fn data_path(file: &str) -> PathBuf {
+    PathBuf::from(file)
+}
+
+pub fn now() -> Timestamp {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -2,34 +2,33 @@ use crate::components::*;
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
 use bevy_rapier2d::prelude::*;
-use libm::{self, Libm};
-use std::{
-    collections::{HashMap, HashSet},
-    vec,
-};
+use std::collections::{BTreeMap, HashSet};
 use bevy::render::camera::ScalingMode;
 
-use crate::graphics::*;
-
-use crate::resources::MouseLoc;
-use crate::game::player::components::Player;
-use crate::game::enemies::components::Zombie;
-
-pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+use crate::events::{BulletHit, GrenadeExploded, LevelEntered, LevelExited};
+use crate::resources::{LoadedLevels, MouseLoc, ScreenShake};
+use crate::settings::components::Settings;
+use crate::game::player::components::{Player, PlayerActions, PlayerId};
+use crate::game::enemies::components::{DamageSource, LastHit, Zombie};
+use crate::AppState;
+
+/// Menu button with marker `B` whose interaction changed this frame
+pub type ButtonInteraction<'w, 's, B> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<B>)
+>;
+
+pub fn setup(mut commands: Commands) {
     let camera = Camera2dBundle {
         projection: OrthographicProjection {
-            scaling_mode: ScalingMode::Fixed { width: 348.0, height: 195.0 },
+            scaling_mode: ScalingMode::Fixed { width: CAMERA_VIEW.x, height: CAMERA_VIEW.y },
             ..default()
         },
             ..default()
     };
     commands.spawn((camera, MainCamera));
-
-    let ldtk_handle = asset_server.load("test.ldtk");
-    commands.spawn(LdtkWorldBundle {
-        ldtk_handle,
-        ..Default::default()
-    });
 }
 
 pub fn mouse_movement_updating_system(
@@ -48,38 +47,98 @@ pub fn mouse_movement_updating_system(
     }
 }
 
-pub fn update_level_selection(
+/// How far into a level the player has to walk before it gets selected,
+/// so standing on the border of two levels doesn't flip between them
+const LEVEL_BORDER_MARGIN: f32 = 8.0;
+
+pub fn update_loaded_levels(
     level_query: Query<(&Handle<LdtkLevel>, &Transform), Without<Player>>,
-    player_query: Query<&Transform, With<Player>>,
-    mut level_selection: ResMut<LevelSelection>,
     ldtk_levels: Res<Assets<LdtkLevel>>,
+    mut loaded_levels: ResMut<LoadedLevels>,
 ) {
-    for (level_handle, level_transform) in &level_query {
-        if let Some(ldtk_level) = ldtk_levels.get(level_handle) {
-            let level_bounds = Rect {
-                min: Vec2::new(level_transform.translation.x, level_transform.translation.y),
-                max: Vec2::new(
-                    level_transform.translation.x + ldtk_level.level.px_wid as f32,
-                    level_transform.translation.y + ldtk_level.level.px_hei as f32,
-                ),
-            };
-
-            for player_transform in &player_query {
-                //dbg!(player_transform);
-                if (player_transform.translation.x < level_bounds.max.x
-                    && player_transform.translation.x > level_bounds.min.x
-                    && player_transform.translation.y < level_bounds.max.y
-                    && player_transform.translation.y > level_bounds.min.y)
-                    && !level_selection.is_match(&0, &ldtk_level.level)
-                {
-                    println!("Changing level");
-                    *level_selection = LevelSelection::Iid(ldtk_level.level.iid.clone());
-                }
+    loaded_levels.levels = level_query
+        .iter()
+        .filter_map(|(level_handle, level_transform)| {
+            let ldtk_level = ldtk_levels.get(level_handle)?;
+            let min = level_transform.translation.truncate();
+            let size = Vec2::new(ldtk_level.level.px_wid as f32, ldtk_level.level.px_hei as f32);
+            Some((ldtk_level.level.iid.clone(), Rect { min, max: min + size }))
+        })
+        .collect();
+}
+
+pub fn update_level_selection(
+    loaded_levels: Res<LoadedLevels>,
+    player_query: Query<(&Transform, &PlayerId), With<Player>>,
+    mut level_selection: ResMut<LevelSelection>,
+    mut current_level: Local<Option<String>>,
+    mut entered_events: EventWriter<LevelEntered>,
+    mut exited_events: EventWriter<LevelExited>,
+) {
+    // The world got replaced, a level with the same iid in the new one is entered again
+    if current_level.as_deref().map_or(false, |iid| loaded_levels.bounds(iid).is_none()) {
+        if let Some(previous) = current_level.take() {
+            exited_events.send(LevelExited { iid: previous });
+        }
+    }
+
+    // The selection follows player one, or the lowest slot still playing
+    let Some((player_transform, _)) = player_query.iter().min_by_key(|(_, player_id)| player_id.0) else {
+        return;
+    };
+    let position = player_transform.translation.truncate();
+
+    // Stay in the current level until the player has left it
+    let current_bounds = current_level
+        .as_deref()
+        .and_then(|iid| loaded_levels.bounds(iid));
+    if current_bounds.map_or(false, |bounds| bounds.contains(position)) {
+        return;
+    }
+
+    let margin = Vec2::splat(LEVEL_BORDER_MARGIN);
+    let Some((iid, _)) = loaded_levels.levels.iter().find(|(_, bounds)| {
+        Rect { min: bounds.min + margin, max: bounds.max - margin }.contains(position)
+    }) else {
+        return;
+    };
+
+    println!("Changing level");
+    if let Some(previous) = current_level.take() {
+        exited_events.send(LevelExited { iid: previous });
+    }
+    *level_selection = LevelSelection::Iid(iid.clone());
+    entered_events.send(LevelEntered { iid: iid.clone() });
+    *current_level = Some(iid.clone());
+}
+
+/// Cleanup for `OnExit(state)`, removes everything scoped to `state`
+pub fn despawn_state_scoped(
+    state: AppState,
+) -> impl FnMut(Commands, Query<(Entity, &StateScoped)>) {
+    move |mut commands, scoped_query| {
+        for (entity, scoped) in scoped_query.iter() {
+            if scoped.0 == state {
+                commands.entity(entity).despawn_recursive();
             }
         }
     }
 }
 
+/// Bullets aren't part of any level, so they don't go away with one. Once they fly out of
+/// every loaded level there is nothing left to hit.
+pub fn despawn_stray_bullets(
+    mut commands: Commands,
+    loaded_levels: Res<LoadedLevels>,
+    bullet_query: Query<(Entity, &Transform), With<Bullet>>,
+) {
+    for (bullet, transform) in bullet_query.iter() {
+        if !loaded_levels.contains(transform.translation.truncate()) {
+            commands.entity(bullet).despawn_recursive();
+        }
+    }
+}
+
 // pub fn update_level_selection(
 //     level_query: Query<(&Handle<LdtkLevel>, &Transform), Without<Player>>,
 //     player_query: Query<&Transform, With<Player>>,
@@ -111,15 +170,119 @@ pub fn update_level_selection(
 //     }
 // }
 
+/// Every moving body is drawn between its last two fixed steps, by a child so physics never sees it
+#[allow(clippy::type_complexity)]
+pub fn add_interpolation(
+    mut commands: Commands,
+    body_query: Query<(Entity, &Transform, &RigidBody), (Changed<RigidBody>, Without<Interpolated>)>,
+) {
+    for (entity, transform, rigid_body) in body_query.iter() {
+        if *rigid_body != RigidBody::Dynamic {
+            continue;
+        }
+        let sprite = commands
+            .spawn((
+                SpatialBundle {
+                    // Drawn while the body is hidden
+                    visibility: Visibility::Visible,
+                    ..default()
+                },
+                InterpolatedSprite,
+            ))
+            .id();
+        commands
+            .entity(entity)
+            .insert(Interpolated {
+                previous: transform.translation,
+                current: transform.translation,
+                rendered: transform.translation,
+                sprite,
+            })
+            .insert(Visibility::Hidden)
+            .add_child(sprite);
+    }
+}
+
+/// The sprite child draws whatever the body would
+#[allow(clippy::type_complexity)]
+pub fn copy_interpolated_sprites(
+    mut commands: Commands,
+    sprite_query: Query<
+        (&Interpolated, &Sprite, &Handle<Image>),
+        Or<(Added<Interpolated>, Changed<Sprite>, Changed<Handle<Image>>)>
+    >,
+    atlas_query: Query<
+        (&Interpolated, &TextureAtlasSprite, &Handle<TextureAtlas>),
+        Or<(Added<Interpolated>, Changed<TextureAtlasSprite>, Changed<Handle<TextureAtlas>>)>
+    >,
+) {
+    for (interpolated, sprite, image) in sprite_query.iter() {
+        commands.entity(interpolated.sprite).insert((sprite.clone(), image.clone()));
+    }
+    for (interpolated, sprite, atlas) in atlas_query.iter() {
+        commands.entity(interpolated.sprite).insert((sprite.clone(), atlas.clone()));
+    }
+}
+
+/// Bodies are mostly despawned on their own, their sprites go with them
+pub fn despawn_orphaned_sprites(
+    mut commands: Commands,
+    sprite_query: Query<(Entity, &Parent), With<InterpolatedSprite>>,
+    body_query: Query<(), With<Interpolated>>,
+) {
+    for (sprite, body) in sprite_query.iter() {
+        if !body_query.contains(body.get()) {
+            commands.entity(sprite).despawn();
+        }
+    }
+}
+
+pub fn store_simulated_transforms(
+    mut body_query: Query<(&Transform, &mut Interpolated)>,
+) {
+    for (transform, mut interpolated) in body_query.iter_mut() {
+        interpolated.previous = interpolated.current;
+        interpolated.current = transform.translation;
+    }
+}
+
+/// Draws bodies as far between their last two fixed steps as time has moved on since the last one
+pub fn interpolate_transforms(
+    fixed_time: Res<FixedTime>,
+    mut body_query: Query<(&Transform, &mut Interpolated)>,
+    mut sprite_query: Query<&mut Transform, (With<InterpolatedSprite>, Without<Interpolated>)>,
+) {
+    let alpha = (fixed_time.accumulated().as_secs_f32() / fixed_time.period.as_secs_f32()).min(1.0);
+    for (transform, mut interpolated) in body_query.iter_mut() {
+        if transform.translation != interpolated.current {
+            // Moved outside the fixed steps, that's a jump and not something to smooth out
+            interpolated.previous = transform.translation;
+            interpolated.current = transform.translation;
+        }
+        interpolated.rendered = interpolated.previous.lerp(interpolated.current, alpha);
+        let Ok(mut sprite_transform) = sprite_query.get_mut(interpolated.sprite) else {
+            continue;
+        };
+        // Offset from the body, in the body's own space
+        let offset = interpolated.rendered - transform.translation;
+        sprite_transform.translation = transform.rotation.inverse() * offset / transform.scale;
+    }
+}
+
+/// Shake added by a grenade going off
+const GRENADE_SHAKE: f32 = 0.6;
+
 pub fn blow_up_granade(
-    time: Res<Time>,
+    fixed_time: Res<FixedTime>,
     mut grenades: Query<(&mut DetonationTimer, &Transform, Entity), With<Grenade>>,
     mut commands: Commands,
-    mut zombies: Query<(&Transform, &mut Health), With<Zombie>>,
+    mut zombies: Query<(&Transform, &mut Health, &mut LastHit), With<Zombie>>,
+    mut screen_shake: ResMut<ScreenShake>,
+    mut exploded_events: EventWriter<GrenadeExploded>,
     asset_server: Res<AssetServer>,
 ) {
     for (mut det_timer, grenade_transform, entity) in grenades.iter_mut() {
-        det_timer.detonation_timer.tick(time.delta());
+        det_timer.detonation_timer.tick(fixed_time.period);
         if det_timer.detonation_timer.finished() {
             commands.entity(entity).despawn();
             let explosion = commands.spawn(SpriteBundle{
@@ -129,14 +292,17 @@ pub fn blow_up_granade(
             
 
             commands.entity(explosion).despawn();
+            screen_shake.trauma = (screen_shake.trauma + GRENADE_SHAKE).min(1.0);
+            exploded_events.send(GrenadeExploded { position: grenade_transform.translation.truncate() });
 
-            for (zombie_trans, mut zombie_health) in zombies.iter_mut() {
+            for (zombie_trans, mut zombie_health, mut last_hit) in zombies.iter_mut() {
                 if zombie_trans
                     .translation
                     .distance(grenade_transform.translation)
                     < 50.0
                 {
                     zombie_health.health_points -= 10;
+                    last_hit.source = DamageSource::Grenade;
                 }
             }
         }
@@ -145,8 +311,11 @@ pub fn blow_up_granade(
 
 pub fn bullet_collisions(
     mut bullet_collisions: EventReader<CollisionEvent>,
-    mut zombie_query: Query<(&mut Health, Entity, &mut Velocity, &Transform), With<Zombie>>,
+    mut zombie_query: Query<(&mut Health, Entity, &mut Velocity, &Transform, &mut LastHit), With<Zombie>>,
     bullet_query: Query<(&Transform, Entity), With<Bullet>>,
+    shooter_query: Query<&Shooter>,
+    player_query: Query<(), With<Player>>,
+    mut hit_events: EventWriter<BulletHit>,
     mut commands: Commands,
 ) {
     for bullet in bullet_collisions.iter() {
@@ -154,11 +323,20 @@ pub fn bullet_collisions(
         let b = bullet.to_owned();
         match b {
             CollisionEvent::Started(e1, e2, _) => {
+                let shooter = shooter_query.get(e1).or_else(|_| shooter_query.get(e2));
+                let hit_zombie = zombie_query.get(e1).or_else(|_| zombie_query.get(e2));
+                // Only hits of the players count, buddies' shots aren't counted either
+                if let (Ok(shooter), Ok((_, _, _, zombie_transform, _))) = (shooter, hit_zombie) {
+                    if player_query.contains(shooter.0) {
+                        hit_events.send(BulletHit { position: zombie_transform.translation.truncate() });
+                    }
+                }
                 for (bullet_transform, bullet_entity) in bullet_query.iter() {
-                    for (mut health, zombie_entity, mut zombie_vel, zombie_transform) in zombie_query.iter_mut()
+                    for (mut health, zombie_entity, mut zombie_vel, zombie_transform, mut last_hit) in zombie_query.iter_mut()
                     {
                         if zombie_entity.eq(&e1) || zombie_entity.eq(&e2) {
                             health.health_points -= 1;
+                            last_hit.source = DamageSource::Gun;
                             println!(
                                 "Entity: {:?} took 1 dmg and now has: {:?}",
                                 commands.entity(zombie_entity).id(),
@@ -184,31 +362,20 @@ pub fn bullet_collisions(
     }
 }
 
-pub fn spawn_buddy(
-    mut commands: Commands,
-    mouse_pos: Res<MouseLoc>,
-    input: Res<Input<KeyCode>>,
-    characters: Res<CharacterSheet>,
-) {
-    if input.just_pressed(KeyCode::B) {
-        spawn_player_sprite(
-            &mut commands,
-            &characters,
-            Vec3::new(mouse_pos.loc.x.clone(), mouse_pos.loc.y.clone(), 0.0),
-        );
-    }
-}
-
+// Shadow casting data, filled by the commented out part of `spawn_wall_collision`
+#[allow(dead_code)]
 #[derive(Resource)]
 pub struct Edges {
     pub edges: Vec<Edge>,
 }
 
+#[allow(dead_code)]
 #[derive(Resource)]
 pub struct Polygons {
     pub visibility_points: Vec<(f32, f32, f32)>,
 }
 
+#[allow(dead_code)]
 #[derive(Default, Debug)]
 pub struct Edge {
     pub sx: f32,
@@ -217,12 +384,14 @@ pub struct Edge {
     pub ey: f32,
 }
 
+#[allow(dead_code)]
 #[derive(Copy, Clone, Debug)]
 pub struct Cell {
     edge_id: [u32; 4],
     edge_exist: [bool; 4],
 }
 
+#[allow(dead_code)]
 #[derive(Resource)]
 pub struct Cells {
     pub cells: Vec<Vec<Cell>>,
@@ -234,11 +403,11 @@ pub fn spawn_wall_collision(
     parent_query: Query<&Parent, Without<Wall>>,
     level_query: Query<(Entity, &Handle<LdtkLevel>)>,
     levels: Res<Assets<LdtkLevel>>,
-    asset_server: Res<AssetServer>,
+    _asset_server: Res<AssetServer>,
 ) {
     /// Represents a wide wall that is 1 tile tall
     /// Used to spawn wall collisions
-    #[derive(Clone, Eq, PartialEq, Debug, Default, Hash)]
+    #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash)]
     struct Plate {
         left: i32,
         right: i32,
@@ -252,19 +421,20 @@ pub fn spawn_wall_collision(
         bottom: i32,
     }
 
-    const NORTH: usize = 0;
-    const SOUTH: usize = 1;
-    const EAST: usize = 2;
-    const WEST: usize = 3;
+    // const NORTH: usize = 0;
+    // const SOUTH: usize = 1;
+    // const EAST: usize = 2;
+    // const WEST: usize = 3;
 
     // Consider where the walls are
     // storing them as GridCoords in a HashSet for quick, easy lookup
     //
     // The key of this map will be the entity of the level the wall belongs to.
+    // Ordered maps keep the colliders spawning in the same order every time, so replays play back the same.
     // This has two consequences in the resulting collision entities:
     // 1. it forces the walls to be split along level boundaries
     // 2. it lets us easily add the collision entities as children of the appropriate level entity
-    let mut level_to_wall_locations: HashMap<Entity, HashSet<GridCoords>> = HashMap::new();
+    let mut level_to_wall_locations: BTreeMap<Entity, HashSet<GridCoords>> = BTreeMap::new();
 
     wall_query.for_each(|(&grid_coords, parent)| {
         // An intgrid tile's direct parent will be a layer entity, not the level entity
@@ -469,7 +639,7 @@ pub fn spawn_wall_collision(
                 }
 
                 // combine "plates" into rectangles across multiple rows
-                let mut rect_builder: HashMap<Plate, Rect> = HashMap::new();
+                let mut rect_builder: BTreeMap<Plate, Rect> = BTreeMap::new();
                 let mut prev_row: Vec<Plate> = Vec::new();
                 let mut wall_rects: Vec<Rect> = Vec::new();
 
@@ -533,7 +703,17 @@ pub fn spawn_wall_collision(
     }
 }
 
-const ASPECT_RATIO: f32 = 16. / 9.;
+/// Smallest area the camera shows, it only grows to fit every player in
+pub const CAMERA_VIEW: Vec2 = Vec2::new(348.0, 195.0);
+const CAMERA_FRAMING_MARGIN: f32 = 48.0;
+/// Camera offset in pixels at full shake
+const MAX_SHAKE_OFFSET: f32 = 4.0;
+/// Shake lost per second
+const SHAKE_DECAY: f32 = 1.5;
+
+fn drawn_position(transform: &Transform, interpolated: Option<&Interpolated>) -> Vec2 {
+    interpolated.map_or(transform.translation, |interpolated| interpolated.rendered).truncate()
+}
 
 #[allow(clippy::type_complexity)]
 pub fn camera_fit_inside_current_level(
@@ -542,59 +722,57 @@ pub fn camera_fit_inside_current_level(
             &mut bevy::render::camera::OrthographicProjection,
             &mut Transform,
         ),
-        Without<Player>,
-    >,
-    player_query: Query<&Transform, With<Player>>,
-    level_query: Query<
-        (&Transform, &Handle<LdtkLevel>),
-        (Without<OrthographicProjection>, Without<Player>),
+        (With<MainCamera>, Without<Player>),
     >,
-    level_selection: Res<LevelSelection>,
-    ldtk_levels: Res<Assets<LdtkLevel>>,
-    mouse_pos: Res<MouseLoc>,
-    player_pos: Query<&Transform, With<Player>>,
+    player_query: Query<(&Transform, Option<&Interpolated>, &PlayerActions), With<Player>>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    mut screen_shake: ResMut<ScreenShake>,
 ) {
-    if let Ok(Transform {
-        translation: player_translation,
-        ..
-    }) = player_query.get_single()
-    {
-        let player_translation = *player_translation;
-
-        let (mut orthographic_projection, mut camera_transform) = camera_query.single_mut();
-
-        for (level_transform, level_handle) in &level_query {
-            if let Some(ldtk_level) = ldtk_levels.get(level_handle) {
-                let level = &ldtk_level.level;
-                if level_selection.is_match(&0, level) {
-                    orthographic_projection.viewport_origin = Vec2::ZERO;
-
-                    let height = (level.px_hei as f32 / 9.).round() * 9. / 1.7;
-                    let width = height * ASPECT_RATIO;
- //                   orthographic_projection.scaling_mode =
-          //              bevy::render::camera::ScalingMode::Fixed { width, height };
-
-                    if let Ok(player_position) = player_pos.get_single() {
-                        let camera_pos_offset =
-                            (mouse_pos.loc - player_position.translation.truncate()).normalize();
-                        let distance = mouse_pos
-                            .loc
-                            .distance(player_position.translation.truncate());
-
-                        camera_transform.translation.x =
-                            (player_translation.x - level_transform.translation.x - width / 2.
-                                + camera_pos_offset.x * distance / 5.0);
-                                //.clamp(0., level.px_wid as f32 - width);
-                        camera_transform.translation.y =
-                            (player_translation.y - level_transform.translation.y - height / 2.
-                                + camera_pos_offset.y * distance / 5.0);
-                                //.clamp(0., level.px_hei as f32 - height);
-
-                        camera_transform.translation.x += level_transform.translation.x;
-                        camera_transform.translation.y += level_transform.translation.y;
-                    }
-                }
-            }
-        }
-    }
+    let Ok((mut orthographic_projection, mut camera_transform)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let mut players = player_query.iter();
+    let Some((first_transform, first_interpolated, first_actions)) = players.next() else {
+        return;
+    };
+
+    // Follows the players where they are drawn, not where they were at the last fixed step
+    let first_position = drawn_position(first_transform, first_interpolated);
+    let (center, view) = if players.len() == 0 {
+        // Single player, look ahead towards where they aim
+        let camera_pos_offset = (first_actions.aim - first_position).normalize_or_zero();
+        let distance = first_actions.aim.distance(first_position);
+        (first_position + camera_pos_offset * distance * settings.camera_look_ahead, CAMERA_VIEW)
+    } else {
+        // Several players share the screen, frame all of them
+        let (min, max) = player_query.iter().fold(
+            (first_position, first_position),
+            |(min, max), (transform, interpolated, _)| {
+                let position = drawn_position(transform, interpolated);
+                (min.min(position), max.max(position))
+            },
+        );
+        let needed = max - min + Vec2::splat(CAMERA_FRAMING_MARGIN * 2.0);
+        let scale = (needed.x / CAMERA_VIEW.x).max(needed.y / CAMERA_VIEW.y).max(1.0);
+        ((min + max) / 2.0, CAMERA_VIEW * scale)
+    };
+
+    orthographic_projection.viewport_origin = Vec2::ZERO;
+    orthographic_projection.scaling_mode = ScalingMode::Fixed {
+        width: view.x,
+        height: view.y,
+    };
+    // Jitters around quickly, shrinking as the shake fades
+    let shake = if settings.screen_shake {
+        let t = time.elapsed_seconds();
+        Vec2::new((t * 47.0).sin(), (t * 61.0).cos()) * screen_shake.trauma.powi(2) * MAX_SHAKE_OFFSET
+    } else {
+        Vec2::ZERO
+    };
+    screen_shake.trauma = (screen_shake.trauma - SHAKE_DECAY * time.delta_seconds()).max(0.0);
+
+    camera_transform.translation.x = center.x - view.x / 2.0 + shake.x;
+    camera_transform.translation.y = center.y - view.y / 2.0 + shake.y;
 }